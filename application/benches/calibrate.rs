@@ -0,0 +1,170 @@
+use application::days::one::{
+    calibrate, default_number_provider::DefaultNumberProvider,
+    text_number_provider::TextNumberProvider,
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../../challenges/one/calibrate_full.txt");
+
+/// A frozen copy of the calibration as it was before providers had a per line
+/// API: every number of the input is collected into a `Vec<Vec<u32>>` first,
+/// and spelled out numbers are matched on a `Vec<char>` of the whole text.
+mod baseline {
+    use std::collections::HashMap;
+
+    pub enum Trie {
+        Value(u32),
+        SubTree(HashMap<char, Trie>),
+    }
+
+    impl Trie {
+        pub fn new(elements: Vec<(&str, u32)>) -> Trie {
+            if elements.len() == 1 && elements[0].0.is_empty() {
+                return Trie::Value(elements[0].1);
+            }
+
+            let mut char_map: HashMap<char, Vec<(&str, u32)>> = HashMap::new();
+            for element in elements {
+                let mut chars = element.0.chars();
+
+                let first_char = chars.next().unwrap();
+                char_map
+                    .entry(first_char)
+                    .or_default()
+                    .push((chars.as_str(), element.1));
+            }
+
+            let mut final_map: HashMap<char, Trie> = HashMap::new();
+            for char_mapping in char_map {
+                final_map.insert(char_mapping.0, Trie::new(char_mapping.1));
+            }
+
+            return Trie::SubTree(final_map);
+        }
+
+        fn match_at(&self, text: &[char], start_index: usize) -> Option<u32> {
+            return match self {
+                Trie::Value(val) => Some(*val),
+                Trie::SubTree(map) => {
+                    if text.len() == start_index {
+                        return None;
+                    }
+
+                    return map.get(&text[start_index])?.match_at(text, start_index + 1);
+                }
+            };
+        }
+    }
+
+    pub fn numbers_trie() -> Trie {
+        return Trie::new(vec![
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ]);
+    }
+
+    pub fn extract_digits(text: &str) -> Vec<Vec<u32>> {
+        let mut i = 0;
+        let mut res: Vec<Vec<u32>> = vec![vec![]];
+
+        for c in text.chars() {
+            match c {
+                '\n' => {
+                    i += 1;
+                    res.push(vec![]);
+                }
+                '0'..='9' => {
+                    res[i].push(c.to_digit(10).expect(""));
+                }
+                _ => continue,
+            }
+        }
+
+        return res;
+    }
+
+    pub fn extract_text(text: &str, trie: &Trie) -> Vec<Vec<u32>> {
+        let mut i = 0;
+        let mut res: Vec<Vec<u32>> = vec![vec![]];
+        let chars: Vec<char> = text.chars().collect();
+
+        for (char_i, c) in chars.iter().enumerate() {
+            match c {
+                '\n' => {
+                    i += 1;
+                    res.push(vec![]);
+                }
+                _ => {
+                    let r = match c {
+                        '0'..='9' => c.to_digit(10),
+                        'o' | 't' | 'f' | 's' | 'e' | 'n' => trie.match_at(&chars, char_i),
+                        _ => None,
+                    };
+                    match r {
+                        None => continue,
+                        Some(val) => res[i].push(val),
+                    }
+                }
+            }
+        }
+
+        return res;
+    }
+
+    pub fn calibrate(numbers: Vec<Vec<u32>>) -> Vec<u32> {
+        let mut res = vec![];
+
+        for line in numbers {
+            if line.is_empty() {
+                continue;
+            }
+            let first_digit = line[0];
+            let last_digit = line[line.len() - 1];
+
+            res.push(first_digit * 10 + last_digit);
+        }
+
+        return res;
+    }
+}
+
+fn bench_default(c: &mut Criterion) {
+    assert_eq!(
+        baseline::calibrate(baseline::extract_digits(INPUT)),
+        calibrate(INPUT, &DefaultNumberProvider {}).unwrap()
+    );
+    let mut group = c.benchmark_group("default_number_provider");
+    group.bench_function("collected", |b| {
+        b.iter(|| baseline::calibrate(baseline::extract_digits(black_box(INPUT))))
+    });
+    group.bench_function("first_last", |b| {
+        b.iter(|| calibrate(black_box(INPUT), &DefaultNumberProvider {}))
+    });
+    group.finish();
+}
+
+fn bench_text(c: &mut Criterion) {
+    let trie = baseline::numbers_trie();
+    assert_eq!(
+        baseline::calibrate(baseline::extract_text(INPUT, &trie)),
+        calibrate(INPUT, &TextNumberProvider::default()).unwrap()
+    );
+    let mut group = c.benchmark_group("text_number_provider");
+    group.bench_function("collected", |b| {
+        b.iter(|| baseline::calibrate(baseline::extract_text(black_box(INPUT), &trie)))
+    });
+    group.bench_function("first_last", |b| {
+        b.iter(|| calibrate(black_box(INPUT), &TextNumberProvider::default()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_default, bench_text);
+criterion_main!(benches);
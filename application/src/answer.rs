@@ -0,0 +1,196 @@
+//! Wide, overflow checked puzzle answers.
+//!
+//! Answers are sums and products of many small values, on large generated
+//! inputs those no longer fit the types the values are parsed as. `Answer`
+//! stores them as a `u128` and reports overflow instead of wrapping.
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Answer(u128);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnswerError {
+    Overflow,
+    Negative,
+    /// A number in the input does not fit the type it is read as.
+    NumberTooLarge,
+}
+
+impl Display for AnswerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            AnswerError::Overflow => write!(f, "answer is too large for its type"),
+            AnswerError::Negative => write!(f, "answer can not be built from a negative value"),
+            AnswerError::NumberTooLarge => write!(f, "a number in the input is too large"),
+        };
+    }
+}
+
+impl std::error::Error for AnswerError {}
+
+impl Answer {
+    pub const ZERO: Answer = Answer(0);
+    pub const ONE: Answer = Answer(1);
+
+    pub fn value(&self) -> u128 {
+        return self.0;
+    }
+
+    pub fn checked_add(self, other: Answer) -> Result<Answer, AnswerError> {
+        return self
+            .0
+            .checked_add(other.0)
+            .map(Answer)
+            .ok_or(AnswerError::Overflow);
+    }
+
+    pub fn checked_mul(self, other: Answer) -> Result<Answer, AnswerError> {
+        return self
+            .0
+            .checked_mul(other.0)
+            .map(Answer)
+            .ok_or(AnswerError::Overflow);
+    }
+
+    /// Sums all values, a negative value is reported as an error just like
+    /// overflow.
+    pub fn sum<T: IntoAnswer, I: IntoIterator<Item = T>>(values: I) -> Result<Answer, AnswerError> {
+        let mut res = Answer::ZERO;
+        for value in values {
+            res = res.checked_add(value.into_answer()?)?;
+        }
+        return Ok(res);
+    }
+
+    pub fn product<T: IntoAnswer, I: IntoIterator<Item = T>>(
+        values: I,
+    ) -> Result<Answer, AnswerError> {
+        let mut res = Answer::ONE;
+        for value in values {
+            res = res.checked_mul(value.into_answer()?)?;
+        }
+        return Ok(res);
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+/// Values an answer can be built from.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, AnswerError>;
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> Result<Answer, AnswerError> {
+        return Ok(self);
+    }
+}
+
+/// Lets results of earlier checked computations be summed directly, the
+/// first error is passed on.
+impl<T: IntoAnswer> IntoAnswer for Result<T, AnswerError> {
+    fn into_answer(self) -> Result<Answer, AnswerError> {
+        return self?.into_answer();
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    return Answer(value as u128);
+                }
+            }
+
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Result<Answer, AnswerError> {
+                    return Ok(Answer::from(self));
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! try_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<$t> for Answer {
+                type Error = AnswerError;
+
+                fn try_from(value: $t) -> Result<Self, Self::Error> {
+                    if value < 0 {
+                        return Err(AnswerError::Negative);
+                    }
+                    return Ok(Answer(value as u128));
+                }
+            }
+
+            impl IntoAnswer for $t {
+                fn into_answer(self) -> Result<Answer, AnswerError> {
+                    return Answer::try_from(self);
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+try_from_signed!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sum_wide() {
+        let res = Answer::sum(vec![u32::MAX, u32::MAX, 2]).unwrap();
+        assert_eq!(res.value(), 2 * u32::MAX as u128 + 2);
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let res = Answer::sum(vec![u128::MAX, 1]);
+        assert_eq!(res, Err(AnswerError::Overflow));
+    }
+
+    #[test]
+    fn sum_negative() {
+        let res = Answer::sum(vec![3, -1, 4]);
+        assert_eq!(res, Err(AnswerError::Negative));
+    }
+
+    #[test]
+    fn product_wide() {
+        let res = Answer::product(vec![i32::MAX, i32::MAX, i32::MAX]).unwrap();
+        assert_eq!(res.value(), (i32::MAX as u128).pow(3));
+    }
+
+    #[test]
+    fn product_overflow() {
+        let res = Answer::product(vec![u64::MAX, u64::MAX, 2]);
+        assert_eq!(res, Err(AnswerError::Overflow));
+    }
+
+    #[test]
+    fn sum_results() {
+        let res = Answer::sum(vec![
+            Ok(Answer::from(3u32)),
+            Answer::product(vec![u128::MAX, 2]),
+        ]);
+        assert_eq!(res, Err(AnswerError::Overflow));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(Answer::sum(Vec::<u32>::new()), Ok(Answer::ZERO));
+        assert_eq!(Answer::product(Vec::<u32>::new()), Ok(Answer::ONE));
+    }
+}
@@ -2,26 +2,124 @@ use super::number_provider::NumberProvider;
 
 pub struct DefaultNumberProvider {}
 
+/// Bytes checked at once, the loop over a chunk has no early exit so the
+/// compiler can vectorise it.
+const CHUNK_SIZE: usize = 16;
+
+/// The digits of a line, can be walked from both ends.
+pub struct Digits<'a> {
+    line: &'a str,
+    /// Byte offset of the first unvisited byte.
+    front: usize,
+    /// Byte offset just past the last unvisited byte.
+    back: usize,
+}
+
+impl<'a> Iterator for Digits<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.front + find_first_digit(&self.line[self.front..self.back])?;
+        self.front = position + 1;
+        return Some(digit_value(self.line, position));
+    }
+}
+
+impl<'a> DoubleEndedIterator for Digits<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let position = self.front + find_last_digit(&self.line[self.front..self.back])?;
+        self.back = position;
+        return Some(digit_value(self.line, position));
+    }
+}
+
 impl NumberProvider for DefaultNumberProvider {
-    fn extract_numbers(&self, text: &str) -> Vec<Vec<u32>> {
-        let mut i = 0;
-        let mut res: Vec<Vec<u32>> = vec![vec![]];
-
-        for c in text.chars() {
-            match c {
-                '\n' => {
-                    i += 1;
-                    res.push(vec![]);
-                },
-                '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8'| '9' => {
-                    res[i].push(c.to_digit(10).expect(""));
-                },
-                _ => continue
-            }
+    type Numbers<'a> = Digits<'a>;
+
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a> {
+        return Digits {
+            line,
+            front: 0,
+            back: line.len(),
+        };
+    }
+
+    fn first(&self, line: &str) -> Option<u32> {
+        return find_first_digit(line).map(|x| digit_value(line, x));
+    }
+
+    fn last(&self, line: &str) -> Option<u32> {
+        return find_last_digit(line).map(|x| digit_value(line, x));
+    }
+}
+
+fn digit_value(line: &str, position: usize) -> u32 {
+    return (line.as_bytes()[position] - b'0') as u32;
+}
+
+/// Returns whether the chunk contains a digit and whether it contains a
+/// byte that is not ASCII.
+fn scan_chunk(chunk: &[u8]) -> (bool, bool) {
+    let mut has_digit = false;
+    let mut has_non_ascii = false;
+    for byte in chunk {
+        has_digit |= byte.is_ascii_digit();
+        has_non_ascii |= !byte.is_ascii();
+    }
+    return (has_digit, has_non_ascii);
+}
+
+/// Byte offset of the first digit in `line`.
+fn find_first_digit(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut start = 0;
+
+    for chunk in bytes.chunks(CHUNK_SIZE) {
+        let (has_digit, has_non_ascii) = scan_chunk(chunk);
+        if has_non_ascii {
+            // Every byte before this chunk is ASCII, so `start` is a char boundary.
+            return line[start..]
+                .char_indices()
+                .find(|(_, c)| c.is_ascii_digit())
+                .map(|(i, _)| start + i);
+        }
+        if has_digit {
+            return chunk
+                .iter()
+                .position(|x| x.is_ascii_digit())
+                .map(|x| start + x);
         }
+        start += chunk.len();
+    }
 
-        return res;
+    return None;
+}
+
+/// Byte offset of the last digit in `line`.
+fn find_last_digit(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut end = bytes.len();
+
+    for chunk in bytes.rchunks(CHUNK_SIZE) {
+        let (has_digit, has_non_ascii) = scan_chunk(chunk);
+        if has_non_ascii {
+            // Every byte after this chunk is ASCII, so `end` is a char boundary.
+            return line[..end]
+                .char_indices()
+                .rev()
+                .find(|(_, c)| c.is_ascii_digit())
+                .map(|(i, _)| i);
+        }
+        end -= chunk.len();
+        if has_digit {
+            return chunk
+                .iter()
+                .rposition(|x| x.is_ascii_digit())
+                .map(|x| end + x);
+        }
     }
+
+    return None;
 }
 
 #[cfg(test)]
@@ -29,6 +127,8 @@ mod tests {
     use crate::days::one::default_number_provider::DefaultNumberProvider;
 
     use super::*;
+    use crate::days::one::text_number_provider::TextNumberProvider;
+    use proptest::prelude::*;
 
     #[test]
     fn extract_singleline() {
@@ -61,4 +161,65 @@ a1b2c3d4e5f");
         assert_eq!(res[2][3], 4);
         assert_eq!(res[2][4], 5);
     }
+
+    #[test]
+    fn first_and_last() {
+        let sut = DefaultNumberProvider {};
+        assert_eq!(sut.first("pqr3stu8vwx"), Some(3));
+        assert_eq!(sut.last("pqr3stu8vwx"), Some(8));
+        assert_eq!(sut.last("abc"), None);
+    }
+
+    #[test]
+    fn first_and_last_across_chunks() {
+        let sut = DefaultNumberProvider {};
+        let line = "abcdefghijklmnopqrstuvwxyz5abcdefghijklmnopqrstuvwxyz6abcdefghijklmnop";
+        assert_eq!(sut.first(line), Some(5));
+        assert_eq!(sut.last(line), Some(6));
+    }
+
+    #[test]
+    fn non_ascii_line() {
+        let sut = DefaultNumberProvider {};
+        let line = "ünïcödé → 4 and ४ and 9 ½";
+        assert_eq!(sut.first(line), Some(4));
+        assert_eq!(sut.last(line), Some(9));
+        assert_eq!(sut.numbers(line).collect::<Vec<u32>>(), vec![4, 9]);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_char_path(text in any::<String>()) {
+            let sut = DefaultNumberProvider {};
+            let expected: Vec<u32> = text.chars().filter_map(|x| x.to_digit(10)).collect();
+            prop_assert_eq!(sut.numbers(&text).collect::<Vec<u32>>(), expected.clone());
+            prop_assert_eq!(sut.first(&text), expected.first().copied());
+            prop_assert_eq!(sut.last(&text), expected.last().copied());
+
+            let mut backwards: Vec<u32> = sut.numbers(&text).rev().collect();
+            backwards.reverse();
+            prop_assert_eq!(backwards, expected);
+        }
+
+        #[test]
+        fn matches_text_provider_without_letters(text in "[0-9 .,;:!?€→-]{0,100}") {
+            let sut = DefaultNumberProvider {};
+            let text_provider = TextNumberProvider::default();
+            prop_assert_eq!(sut.first(&text), text_provider.first(&text));
+            prop_assert_eq!(sut.last(&text), text_provider.last(&text));
+        }
+
+        #[test]
+        fn extract_line_count_matches_input(text in "[a-z0-9\n]{0,200}") {
+            let res = DefaultNumberProvider {}.extract_numbers(&text);
+            prop_assert_eq!(res.len(), text.split('\n').count());
+        }
+
+        #[test]
+        fn extract_keeps_every_digit(text in "[a-z0-9]{0,50}") {
+            let res = DefaultNumberProvider {}.extract_numbers(&text);
+            let digits: Vec<u32> = text.chars().filter_map(|x| x.to_digit(10)).collect();
+            prop_assert_eq!(&res[0], &digits);
+        }
+    }
 }
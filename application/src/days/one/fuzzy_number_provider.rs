@@ -0,0 +1,206 @@
+use std::iter::Map;
+use std::str::CharIndices;
+
+use super::number_provider::NumberProvider;
+use super::text_number_provider::TRIE;
+
+/// Recognises digits and spelled numbers like `TextNumberProvider`, and also
+/// spelled numbers with up to `max_distance` typos such as `sevn` or `fuur`.
+///
+/// Exact matches win, a misspelled number is only read where it does not
+/// overlap an exact match or an earlier number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyNumberProvider {
+    pub max_distance: usize,
+}
+
+impl Default for FuzzyNumberProvider {
+    fn default() -> Self {
+        return FuzzyNumberProvider { max_distance: 1 };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NumberMatch<'a> {
+    pub value: u32,
+    /// Offset in bytes from the start of the line.
+    pub byte_offset: usize,
+    /// The text the number was read from.
+    pub text: &'a str,
+    /// Edits needed to turn `text` into the spelled number, 0 when exact.
+    pub distance: usize,
+}
+
+impl FuzzyNumberProvider {
+    /// Every number of the line with the text it was read from.
+    pub fn number_matches<'a>(&self, line: &'a str) -> NumberMatches<'a> {
+        return NumberMatches {
+            max_distance: self.max_distance,
+            line,
+            chars: line.char_indices(),
+            covered: 0,
+        };
+    }
+
+    /// The numbers of the line that were read from a misspelling.
+    pub fn corrections<'a>(&self, line: &'a str) -> Vec<NumberMatch<'a>> {
+        return self
+            .number_matches(line)
+            .filter(|x| x.distance > 0)
+            .collect();
+    }
+}
+
+/// The numbers of a line, see `FuzzyNumberProvider::number_matches`.
+pub struct NumberMatches<'a> {
+    max_distance: usize,
+    line: &'a str,
+    chars: CharIndices<'a>,
+    /// Byte offset up to which the line has been read into numbers.
+    covered: usize,
+}
+
+impl<'a> Iterator for NumberMatches<'a> {
+    type Item = NumberMatch<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.line;
+        for (i, c) in self.chars.by_ref() {
+            let rest = &line[i..];
+            if let Some(value) = c.to_digit(10) {
+                self.covered = self.covered.max(i + 1);
+                return Some(NumberMatch {
+                    value,
+                    byte_offset: i,
+                    text: &rest[..1],
+                    distance: 0,
+                });
+            }
+
+            let Some(found) = TRIE
+                .match_fuzzy(rest, self.max_distance)
+                .into_iter()
+                .find(|x| is_plausible(&rest[..x.length], x.distance))
+            else {
+                continue;
+            };
+            let end = i + found.length;
+            if found.distance > 0 && (i < self.covered || has_exact(line, i + c.len_utf8(), end)) {
+                continue;
+            }
+            self.covered = self.covered.max(end);
+            return Some(NumberMatch {
+                value: found.value,
+                byte_offset: i,
+                text: &line[i..end],
+                distance: found.distance,
+            });
+        }
+        return None;
+    }
+}
+
+/// Whether `text` is close enough to a number to be read as its misspelling,
+/// which needs more than half of its chars to be right. Without this a large
+/// `max_distance` turns stray letters like `x` into numbers.
+fn is_plausible(text: &str, distance: usize) -> bool {
+    return distance == 0 || distance * 2 < text.chars().count();
+}
+
+/// Whether a digit or an exactly spelled number starts between the byte
+/// offsets `from` and `to`.
+fn has_exact(line: &str, from: usize, to: usize) -> bool {
+    return line[from..to]
+        .char_indices()
+        .any(|(i, c)| c.is_ascii_digit() || TRIE.match_prefix(&line[from + i..]).is_some());
+}
+
+impl NumberProvider for FuzzyNumberProvider {
+    type Numbers<'a> = Map<NumberMatches<'a>, fn(NumberMatch<'a>) -> u32>;
+
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a> {
+        return self.number_matches(line).map(|x| x.value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::one::text_number_provider::TextNumberProvider;
+    use proptest::prelude::*;
+
+    #[test]
+    fn extract_misspelled() {
+        let sut = FuzzyNumberProvider::default();
+        let res = sut.extract_numbers("sevn2fuur\nniine\nxsixx");
+        assert_eq!(res, vec![vec![7, 2, 4], vec![9], vec![6]]);
+    }
+
+    #[test]
+    fn corrections_report_text() {
+        let sut = FuzzyNumberProvider::default();
+        let res = sut.corrections("sevn2fuur");
+        assert_eq!(
+            res,
+            vec![
+                NumberMatch {
+                    value: 7,
+                    byte_offset: 0,
+                    text: "sevn",
+                    distance: 1
+                },
+                NumberMatch {
+                    value: 4,
+                    byte_offset: 5,
+                    text: "fuur",
+                    distance: 1
+                },
+            ]
+        );
+        assert_eq!(sut.corrections("seven2four"), vec![]);
+    }
+
+    #[test]
+    fn prefers_exact_matches() {
+        let sut = FuzzyNumberProvider::default();
+        assert_eq!(sut.numbers("xone").collect::<Vec<u32>>(), vec![1]);
+        assert_eq!(sut.numbers("eightwo").collect::<Vec<u32>>(), vec![8, 2]);
+        assert_eq!(sut.numbers("se7en").collect::<Vec<u32>>(), vec![7]);
+        assert_eq!(sut.numbers("seven").collect::<Vec<u32>>(), vec![7]);
+    }
+
+    #[test]
+    fn larger_distance() {
+        let sut = FuzzyNumberProvider { max_distance: 2 };
+        let res = sut.corrections("eivgt");
+        assert_eq!((res[0].value, res[0].distance), (8, 2));
+        assert_eq!(FuzzyNumberProvider::default().first("eivgt"), None);
+    }
+
+    #[test]
+    fn ignores_stray_letters() {
+        let sut = FuzzyNumberProvider { max_distance: 2 };
+        assert_eq!(sut.numbers("xx1oen").collect::<Vec<u32>>(), vec![1]);
+        assert_eq!(sut.numbers("x").collect::<Vec<u32>>(), vec![]);
+        assert_eq!(sut.numbers("sevn").collect::<Vec<u32>>(), vec![7]);
+        assert_eq!(FuzzyNumberProvider::default().first("on"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn without_edits_matches_text_provider(text in "[a-z0-9\n]{0,200}") {
+            let res = FuzzyNumberProvider { max_distance: 0 }.extract_numbers(&text);
+            prop_assert_eq!(res, TextNumberProvider::default().extract_numbers(&text));
+        }
+
+        #[test]
+        fn keeps_exact_numbers(text in "[a-z0-9]{0,50}") {
+            let res: Vec<u32> = FuzzyNumberProvider::default().numbers(&text).collect();
+            let mut res = res.into_iter();
+            for number in TextNumberProvider::default().numbers(&text) {
+                prop_assert!(res.any(|x| x == number));
+            }
+        }
+    }
+}
@@ -1,39 +1,70 @@
 pub mod default_number_provider;
+pub mod fuzzy_number_provider;
 pub mod text_number_provider;
+pub mod unicode_number_provider;
 pub mod number_provider;
+pub mod pattern_number_provider;
+pub mod strategy;
 
-pub fn calibrate(text: &str, number_provider: &dyn number_provider::NumberProvider) -> Vec<u32> {
+use memchr::memchr_iter;
+use number_provider::NumberProvider;
+
+use crate::answer::AnswerError;
+
+/// The first and last number of every line as a two digit value, an error
+/// when a provider's numbers are too large for that to fit a `u32`.
+pub fn calibrate<P: NumberProvider>(
+    text: &str,
+    number_provider: &P,
+) -> Result<Vec<u32>, AnswerError> {
     let mut res = vec![];
-    let numbers = number_provider.extract_numbers(&text);
 
-    for line in numbers {
-        if line.len() == 0 {
+    for line in split_lines(text) {
+        let Some(first_digit) = number_provider.first(line) else {
             continue;
-        }
-        let first_digit = line[0];
-        let last_digit = line[line.len() - 1];
+        };
+        let last_digit = number_provider.last(line).unwrap();
 
-        res.push(first_digit * 10 + last_digit);
+        let value = first_digit
+            .checked_mul(10)
+            .and_then(|x| x.checked_add(last_digit))
+            .ok_or(AnswerError::Overflow)?;
+        res.push(value);
     }
 
-    return res;
+    return Ok(res);
+}
+
+/// Splits on `\n` like `str::split`, using `memchr` to find the newlines.
+fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let ends = memchr_iter(b'\n', text.as_bytes()).chain(std::iter::once(text.len()));
+
+    return ends.map(move |end| {
+        let line = &text[start..end];
+        start = end + 1;
+        return line;
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use crate::days::one::default_number_provider::DefaultNumberProvider;
+    use crate::days::one::pattern_number_provider::PatternNumberProvider;
+    use crate::days::one::text_number_provider::TextNumberProvider;
 
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn calibrate_singleline() {
-        let res = calibrate("1abc2", &DefaultNumberProvider {});
+        let res = calibrate("1abc2", &DefaultNumberProvider {}).unwrap();
         assert_eq!(res[0], 12);
     }
 
     #[test]
     fn calibrate_one_digit() {
-        let res = calibrate("7abc", &DefaultNumberProvider {});
+        let res = calibrate("7abc", &DefaultNumberProvider {}).unwrap();
         assert_eq!(res[0], 77);
     }
 
@@ -44,9 +75,39 @@ mod tests {
 pqr3stu8vwx
 a1b2c3d4e5f",
             &DefaultNumberProvider {},
-        );
+        )
+        .unwrap();
         assert_eq!(res[0], 12);
         assert_eq!(res[1], 38);
         assert_eq!(res[2], 15);
     }
+
+    #[test]
+    fn calibrate_reports_overflow() {
+        let sut = PatternNumberProvider::parse("x = 4294967295").unwrap();
+        assert_eq!(calibrate("x", &sut), Err(AnswerError::Overflow));
+        let sut = PatternNumberProvider::parse("x = 429496729\ny = 5").unwrap();
+        assert_eq!(calibrate("xy", &sut), Ok(vec![4294967295]));
+    }
+
+    #[test]
+    fn split_lines_like_split() {
+        let text = "a\n\nb\r\nc\n";
+        let res: Vec<&str> = split_lines(text).collect();
+        assert_eq!(res, text.split('\n').collect::<Vec<&str>>());
+    }
+
+    proptest! {
+        #[test]
+        fn calibrate_value_in_range(text in "([a-z]{0,5}[1-9][a-z1-9]{0,10}\n){1,20}") {
+            let res = calibrate(&text, &TextNumberProvider::default()).unwrap();
+            prop_assert!(res.iter().all(|x| (11..=99).contains(x)));
+        }
+
+        #[test]
+        fn calibrate_one_value_per_line(text in "([a-z]{0,5}[0-9][a-z0-9]{0,10}\n){0,20}[a-z]{0,5}[0-9]") {
+            let res = calibrate(&text, &DefaultNumberProvider {}).unwrap();
+            prop_assert_eq!(res.len(), text.lines().count());
+        }
+    }
 }
@@ -1,3 +1,87 @@
 pub trait NumberProvider {
-    fn extract_numbers(&self, text: &str) -> Vec<Vec<u32>>;
+    type Numbers<'a>: Iterator<Item = u32>
+    where
+        Self: 'a;
+
+    /// Iterates the numbers in a single line from left to right.
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a>;
+
+    fn first(&self, line: &str) -> Option<u32> {
+        return self.numbers(line).next();
+    }
+
+    /// Providers that can scan a line from the end should override this,
+    /// the default walks the whole line.
+    fn last(&self, line: &str) -> Option<u32> {
+        return self.numbers(line).last();
+    }
+
+    fn extract_numbers(&self, text: &str) -> Vec<Vec<u32>> {
+        return text
+            .split('\n')
+            .map(|x| self.numbers(x).collect())
+            .collect();
+    }
+}
+
+/// Recognises a number at the start of a piece of text.
+pub trait PrefixMatcher {
+    fn match_prefix(&self, text: &str) -> Option<u32>;
+}
+
+/// The numbers of a line found by trying a `PrefixMatcher` at every char,
+/// can be walked from both ends.
+///
+/// Matches may overlap, with spelled numbers `eightwo` yields both 8 and 2.
+pub struct PrefixMatches<'a, M: ?Sized> {
+    matcher: &'a M,
+    line: &'a str,
+    /// Byte offset of the next start position from the front.
+    front: usize,
+    /// Byte offset just past the next start position from the back.
+    back: usize,
+}
+
+impl<'a, M: PrefixMatcher + ?Sized> PrefixMatches<'a, M> {
+    pub fn new(matcher: &'a M, line: &'a str) -> PrefixMatches<'a, M> {
+        return PrefixMatches {
+            matcher,
+            line,
+            front: 0,
+            back: line.len(),
+        };
+    }
+}
+
+impl<'a, M: PrefixMatcher + ?Sized> Iterator for PrefixMatches<'a, M> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.front < self.back {
+            let rest = &self.line[self.front..];
+            self.front += rest.chars().next().unwrap().len_utf8();
+
+            if let Some(number) = self.matcher.match_prefix(rest) {
+                return Some(number);
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a, M: PrefixMatcher + ?Sized> DoubleEndedIterator for PrefixMatches<'a, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while self.back > self.front {
+            self.back -= self.line[..self.back]
+                .chars()
+                .next_back()
+                .unwrap()
+                .len_utf8();
+
+            if let Some(number) = self.matcher.match_prefix(&self.line[self.back..]) {
+                return Some(number);
+            }
+        }
+        return None;
+    }
 }
@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use super::number_provider::{NumberProvider, PrefixMatcher, PrefixMatches};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PatternRule {
+    pub pattern: String,
+    pub value: u32,
+}
+
+/// Recognises numbers from a list of literal pattern → value rules, so day
+/// one variants need no new provider.
+///
+/// Every char of a line is tried as the start of a match, so matches may
+/// overlap. When several patterns match at the same position the longest
+/// one wins.
+pub struct PatternNumberProvider {
+    /// Sorted from the longest to the shortest pattern.
+    rules: Vec<PatternRule>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PatternErrorKind {
+    MissingSeparator,
+    EmptyPattern,
+    InvalidValue(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PatternError {
+    /// 1-based line number in the rules file.
+    pub line: usize,
+    pub kind: PatternErrorKind,
+}
+
+impl Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        return match &self.kind {
+            PatternErrorKind::MissingSeparator => write!(f, "expected 'pattern = value'"),
+            PatternErrorKind::EmptyPattern => write!(f, "pattern is empty"),
+            PatternErrorKind::InvalidValue(value) => write!(f, "'{}' is not a number", value),
+        };
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl PatternNumberProvider {
+    pub fn new(mut rules: Vec<PatternRule>) -> PatternNumberProvider {
+        rules.sort_by_key(|x| std::cmp::Reverse(x.pattern.len()));
+        return PatternNumberProvider { rules };
+    }
+
+    /// Reads rules written as one `pattern = value` per line.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, the pattern runs
+    /// up to the last `=` and surrounding whitespace is trimmed.
+    pub fn parse(text: &str) -> Result<PatternNumberProvider, PatternError> {
+        let mut rules = vec![];
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |kind| PatternError { line: i + 1, kind };
+            let (pattern, value) = line
+                .rsplit_once('=')
+                .ok_or_else(|| error(PatternErrorKind::MissingSeparator))?;
+            let pattern = pattern.trim();
+            let value = value.trim();
+
+            if pattern.is_empty() {
+                return Err(error(PatternErrorKind::EmptyPattern));
+            }
+            let value = value
+                .parse()
+                .map_err(|_| error(PatternErrorKind::InvalidValue(value.to_string())))?;
+
+            rules.push(PatternRule {
+                pattern: pattern.to_string(),
+                value,
+            });
+        }
+
+        return Ok(PatternNumberProvider::new(rules));
+    }
+}
+
+impl PrefixMatcher for PatternNumberProvider {
+    fn match_prefix(&self, text: &str) -> Option<u32> {
+        return self
+            .rules
+            .iter()
+            .find(|x| text.starts_with(&x.pattern))
+            .map(|x| x.value);
+    }
+}
+
+impl NumberProvider for PatternNumberProvider {
+    type Numbers<'a> = PrefixMatches<'a, PatternNumberProvider>;
+
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a> {
+        return PrefixMatches::new(self, line);
+    }
+
+    fn last(&self, line: &str) -> Option<u32> {
+        return self.numbers(line).next_back();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::one::text_number_provider::TextNumberProvider;
+    use proptest::prelude::*;
+
+    const ENGLISH: &str = include_str!("../../../../challenges/one/patterns_english.txt");
+
+    #[test]
+    fn parse_rules() {
+        let sut = PatternNumberProvider::parse("# hex\n a = 10\n\nff=255\n").unwrap();
+        assert_eq!(
+            sut.rules,
+            vec![
+                PatternRule {
+                    pattern: "ff".to_string(),
+                    value: 255
+                },
+                PatternRule {
+                    pattern: "a".to_string(),
+                    value: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let res = PatternNumberProvider::parse("one = 1\ntwo 2");
+        assert_eq!(
+            res.err(),
+            Some(PatternError {
+                line: 2,
+                kind: PatternErrorKind::MissingSeparator
+            })
+        );
+
+        let res = PatternNumberProvider::parse(" = 1");
+        assert_eq!(res.err().unwrap().kind, PatternErrorKind::EmptyPattern);
+
+        let res = PatternNumberProvider::parse("one = uno");
+        assert_eq!(
+            res.err().unwrap().kind,
+            PatternErrorKind::InvalidValue("uno".to_string())
+        );
+    }
+
+    #[test]
+    fn longest_pattern_wins() {
+        let sut = PatternNumberProvider::parse("I = 1\nII = 2\nIII = 3\nIV = 4\nV = 5").unwrap();
+        let res: Vec<u32> = sut.numbers("xIVxIIIx").collect();
+        assert_eq!(res, vec![4, 5, 3, 2, 1]);
+    }
+
+    #[test]
+    fn overlapping_matches() {
+        let sut = PatternNumberProvider::parse(ENGLISH).unwrap();
+        let res: Vec<u32> = sut.numbers("eightwothree").collect();
+        assert_eq!(res, vec![8, 2, 3]);
+        assert_eq!(sut.first("xtwone3four"), Some(2));
+        assert_eq!(sut.last("xtwone3four"), Some(4));
+    }
+
+    proptest! {
+        #[test]
+        fn english_rules_match_text_provider(text in "[a-z0-9\\n]{0,200}") {
+            let sut = PatternNumberProvider::parse(ENGLISH).unwrap();
+            prop_assert_eq!(sut.extract_numbers(&text), TextNumberProvider::default().extract_numbers(&text));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt::Display;
+
+use crate::answer::{Answer, AnswerError};
+
+use super::{number_provider::NumberProvider, split_lines};
+
+/// How the numbers of a line are combined into its calibration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Combine {
+    /// The first and last number as digits, the rule of the puzzle itself.
+    FirstLast,
+    /// The first `k` and last `k` numbers as digits, lines with fewer than
+    /// `k` numbers use all of them on both sides.
+    FirstLastK(usize),
+    /// Every number of the line as digits.
+    Full,
+    Sum,
+    Product,
+}
+
+/// Built with `CalibrationStrategy::new`, which rejects settings that give
+/// meaningless totals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "StrategyFields"))]
+pub struct CalibrationStrategy {
+    combine: Combine,
+    /// Base the digits of `FirstLast`, `FirstLastK` and `Full` are read in,
+    /// digits are not checked against it.
+    base: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StrategyError {
+    /// Positional values need a base of at least 2.
+    InvalidBase(u32),
+    /// `FirstLastK` has to take at least one number from each end.
+    ZeroK,
+}
+
+impl Display for StrategyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            StrategyError::InvalidBase(base) => write!(f, "base must be at least 2, got {}", base),
+            StrategyError::ZeroK => write!(f, "k must be at least 1"),
+        };
+    }
+}
+
+impl std::error::Error for StrategyError {}
+
+/// The fields of a `CalibrationStrategy` before they are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct StrategyFields {
+    combine: Combine,
+    base: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StrategyFields> for CalibrationStrategy {
+    type Error = StrategyError;
+
+    fn try_from(value: StrategyFields) -> Result<Self, Self::Error> {
+        return CalibrationStrategy::new(value.combine, value.base);
+    }
+}
+
+impl Default for CalibrationStrategy {
+    fn default() -> Self {
+        return CalibrationStrategy {
+            combine: Combine::FirstLast,
+            base: 10,
+        };
+    }
+}
+
+impl CalibrationStrategy {
+    pub fn new(combine: Combine, base: u32) -> Result<CalibrationStrategy, StrategyError> {
+        if base < 2 {
+            return Err(StrategyError::InvalidBase(base));
+        }
+        if combine == Combine::FirstLastK(0) {
+            return Err(StrategyError::ZeroK);
+        }
+        return Ok(CalibrationStrategy { combine, base });
+    }
+
+    pub fn combine(&self) -> Combine {
+        return self.combine;
+    }
+
+    pub fn base(&self) -> u32 {
+        return self.base;
+    }
+
+    fn combine_numbers(&self, numbers: &[u32]) -> Result<Answer, AnswerError> {
+        return match self.combine {
+            Combine::FirstLast => self.positional(&[numbers[0], numbers[numbers.len() - 1]]),
+            Combine::FirstLastK(k) => {
+                let k = k.min(numbers.len());
+                let first = self.positional(&numbers[..k])?;
+                let last = self.positional(&numbers[numbers.len() - k..])?;
+                let shift = Answer::product(vec![self.base; k])?;
+                first.checked_mul(shift)?.checked_add(last)
+            }
+            Combine::Full => self.positional(numbers),
+            Combine::Sum => Answer::sum(numbers.iter().copied()),
+            Combine::Product => Answer::product(numbers.iter().copied()),
+        };
+    }
+
+    fn positional(&self, digits: &[u32]) -> Result<Answer, AnswerError> {
+        let base = Answer::from(self.base);
+        let mut res = Answer::ZERO;
+        for digit in digits {
+            res = res.checked_mul(base)?.checked_add(Answer::from(*digit))?;
+        }
+        return Ok(res);
+    }
+}
+
+/// Calibrates like `calibrate` but combines the numbers of each line with
+/// `strategy`, lines without numbers are skipped.
+pub fn calibrate_with<P: NumberProvider>(
+    text: &str,
+    number_provider: &P,
+    strategy: &CalibrationStrategy,
+) -> Result<Vec<Answer>, AnswerError> {
+    let mut res = vec![];
+    let mut numbers = vec![];
+
+    for line in split_lines(text) {
+        numbers.clear();
+        if strategy.combine == Combine::FirstLast {
+            let Some(first) = number_provider.first(line) else {
+                continue;
+            };
+            numbers.push(first);
+            numbers.push(number_provider.last(line).unwrap());
+        } else {
+            numbers.extend(number_provider.numbers(line));
+            if numbers.is_empty() {
+                continue;
+            }
+        }
+
+        res.push(strategy.combine_numbers(&numbers)?);
+    }
+
+    return Ok(res);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::one::{
+        calibrate, default_number_provider::DefaultNumberProvider,
+        text_number_provider::TextNumberProvider,
+    };
+
+    fn values(text: &str, combine: Combine, base: u32) -> Vec<u128> {
+        let strategy = CalibrationStrategy::new(combine, base).unwrap();
+        return calibrate_with(text, &DefaultNumberProvider {}, &strategy)
+            .unwrap()
+            .iter()
+            .map(|x| x.value())
+            .collect();
+    }
+
+    #[test]
+    fn default_matches_calibrate() {
+        let text = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n7pqrstsixteen";
+        let res =
+            calibrate_with(text, &TextNumberProvider::default(), &Default::default()).unwrap();
+        let expected: Vec<Answer> = calibrate(text, &TextNumberProvider::default())
+            .unwrap()
+            .into_iter()
+            .map(Answer::from)
+            .collect();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn first_last_k() {
+        assert_eq!(
+            values("a1b2c3d4e5f\n7x", Combine::FirstLastK(2), 10),
+            vec![1245, 77]
+        );
+        assert_eq!(values("123", Combine::FirstLastK(5), 10), vec![123123]);
+    }
+
+    #[test]
+    fn full() {
+        assert_eq!(
+            values("a1b2c3d4e5f\nnothing\n0x9", Combine::Full, 10),
+            vec![12345, 9]
+        );
+    }
+
+    #[test]
+    fn sum_and_product() {
+        assert_eq!(values("a1b2c3d4e5f", Combine::Sum, 10), vec![15]);
+        assert_eq!(values("a1b2c3d4e5f", Combine::Product, 10), vec![120]);
+    }
+
+    #[test]
+    fn other_base() {
+        assert_eq!(values("1x0x1x1", Combine::Full, 2), vec![11]);
+        assert_eq!(values("7abc3", Combine::FirstLast, 16), vec![115]);
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let text = "9".repeat(50);
+        let strategy = CalibrationStrategy::new(Combine::Full, 10).unwrap();
+        let res = calibrate_with(&text, &DefaultNumberProvider {}, &strategy);
+        assert_eq!(res, Err(AnswerError::Overflow));
+    }
+
+    #[test]
+    fn rejects_meaningless_settings() {
+        assert_eq!(
+            CalibrationStrategy::new(Combine::Full, 0),
+            Err(StrategyError::InvalidBase(0))
+        );
+        assert_eq!(
+            CalibrationStrategy::new(Combine::FirstLast, 1),
+            Err(StrategyError::InvalidBase(1))
+        );
+        assert_eq!(
+            CalibrationStrategy::new(Combine::FirstLastK(0), 10),
+            Err(StrategyError::ZeroK)
+        );
+        assert!(CalibrationStrategy::new(Combine::FirstLastK(1), 2).is_ok());
+    }
+}
@@ -1,67 +1,72 @@
-use super::number_provider::NumberProvider;
-use lazy_static::lazy_static;
-use util::trie::Trie;
+use super::number_provider::{NumberProvider, PrefixMatcher, PrefixMatches};
+use util::{
+    static_trie,
+    trie::{MatchOptions, Trie},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct TextNumberProvider {
+    /// How spelled numbers are compared, digits always match exactly. `None`
+    /// when the default options are used, which take the faster exact path.
+    options: Option<MatchOptions>,
+}
 
-pub struct TextNumberProvider {}
+impl TextNumberProvider {
+    pub fn new(options: MatchOptions) -> TextNumberProvider {
+        let is_default = options == MatchOptions::default();
+        return TextNumberProvider {
+            options: if is_default { None } else { Some(options) },
+        };
+    }
+}
 
 impl NumberProvider for TextNumberProvider {
-    fn extract_numbers(&self, text: &str) -> Vec<Vec<u32>> {
-        let mut i = 0;
-        let mut res: Vec<Vec<u32>> = vec![vec![]];
-        let chars: Vec<char> = text.chars().collect();
-
-        for (char_i, c) in chars.iter().enumerate() {
-            match c {
-                '\n' => {
-                    i += 1;
-                    res.push(vec![]);
-                }
-                _ => {
-                    let r = get_number(&chars, char_i);
-                    match r {
-                        None => continue,
-                        Some(val) => res[i].push(val),
-                    }
-                }
-            }
-        }
+    type Numbers<'a> = PrefixMatches<'a, TextNumberProvider>;
 
-        return res;
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a> {
+        return PrefixMatches::new(self, line);
     }
-}
 
-lazy_static! {
-    static ref TRIE: Trie = {
-        Trie::new(vec![
-            ("one", 1),
-            ("two", 2),
-            ("three", 3),
-            ("four", 4),
-            ("five", 5),
-            ("six", 6),
-            ("seven", 7),
-            ("eight", 8),
-            ("nine", 9),
-        ])
-    };
+    fn last(&self, line: &str) -> Option<u32> {
+        return self.numbers(line).next_back();
+    }
 }
 
-fn get_number(text: &Vec<char>, i: usize) -> Option<u32> {
-    let c: char = text[i];
-    return match c {
-        '0' | '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => c.to_digit(10),
-        'o' | 't' | 'f' | 's' | 'e' | 'n' => TRIE.match_at(text, i),
-        _ => None,
-    };
+pub(crate) static TRIE: Trie = static_trie![
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+impl PrefixMatcher for TextNumberProvider {
+    /// Matches a digit or spelled number.
+    fn match_prefix(&self, text: &str) -> Option<u32> {
+        let c = text.chars().next()?;
+        return match (c, &self.options) {
+            ('0'..='9', _) => c.to_digit(10),
+            (_, Some(options)) => TRIE.match_prefix_with(text, options),
+            ('o' | 't' | 'f' | 's' | 'e' | 'n', None) => TRIE.match_prefix(text),
+            _ => None,
+        };
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::one::default_number_provider::DefaultNumberProvider;
+    use proptest::prelude::*;
+    use util::trie::Normalisation;
 
     #[test]
     fn extract_singleline() {
-        let sut = TextNumberProvider {};
+        let sut = TextNumberProvider::default();
         let res = sut.extract_numbers("1abc2");
         assert_eq!(res[0][0], 1);
         assert_eq!(res[0][1], 2);
@@ -69,14 +74,14 @@ mod tests {
 
     #[test]
     fn extract_one_digit() {
-        let sut = TextNumberProvider {};
+        let sut = TextNumberProvider::default();
         let res = sut.extract_numbers("7abc");
         assert_eq!(res[0][0], 7);
     }
 
     #[test]
     fn extract_several_lines() {
-        let sut = TextNumberProvider {};
+        let sut = TextNumberProvider::default();
         let res = sut.extract_numbers(
             "1abc2
 pqr3stu8vwx
@@ -95,7 +100,7 @@ a1b2c3d4e5f",
 
     #[test]
     fn extract_with_text_digit() {
-        let sut = TextNumberProvider {};
+        let sut = TextNumberProvider::default();
         let res = sut.extract_numbers(
             "two1nine
 eightwothree
@@ -138,4 +143,78 @@ zoneight234
         assert_eq!(res[6][0], 7);
         assert_eq!(res[6][1], 6);
     }
+
+    #[test]
+    fn first_and_last_overlapping() {
+        let sut = TextNumberProvider::default();
+        assert_eq!(sut.first("xtwone3four"), Some(2));
+        assert_eq!(sut.last("xtwone3four"), Some(4));
+        assert_eq!(sut.first("eightwo"), Some(8));
+        assert_eq!(sut.last("eightwo"), Some(2));
+        assert_eq!(sut.last("abc"), None);
+    }
+
+    #[test]
+    fn multi_byte_line() {
+        let sut = TextNumberProvider::default();
+        let res: Vec<u32> = sut.numbers("ñone→7twö").collect();
+        assert_eq!(res, vec![1, 7]);
+        assert_eq!(sut.last("ñone→7twö"), Some(7));
+    }
+
+    #[test]
+    fn extract_with_options() {
+        let sut = TextNumberProvider::new(MatchOptions {
+            case_insensitive: true,
+            normalisation: Some(Normalisation::Nfkc),
+            ignored: vec!['-', '_'],
+        });
+        let res = sut.extract_numbers("One2NINE\nfi-ve_sIx-\nｓｅｖｅｎ3\n-eight");
+        assert_eq!(res, vec![vec![1, 2, 9], vec![5, 6], vec![7, 3], vec![8]]);
+        assert_eq!(sut.last("xtWO-ne"), Some(1));
+        assert_eq!(TextNumberProvider::default().first("One2"), Some(2));
+    }
+
+    #[test]
+    fn vocabulary() {
+        let res: Vec<u32> = TRIE.iter().map(|x| x.1).collect();
+        assert_eq!(res, vec![8, 5, 4, 9, 1, 7, 6, 3, 2]);
+        assert_eq!(TRIE.depth(), 5);
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_default_without_letters(text in "[0-9 .,;:!?\n-]{0,200}") {
+            let res = TextNumberProvider::default().extract_numbers(&text);
+            prop_assert_eq!(res, DefaultNumberProvider {}.extract_numbers(&text));
+        }
+
+        #[test]
+        fn ignoring_case_matches_lowercase(text in "[a-zA-Z0-9]{0,50}") {
+            let sut = TextNumberProvider::new(MatchOptions {
+                case_insensitive: true,
+                ..Default::default()
+            });
+            let res = sut.extract_numbers(&text);
+            prop_assert_eq!(res, TextNumberProvider::default().extract_numbers(&text.to_lowercase()));
+        }
+
+        #[test]
+        fn back_matches_front(text in "[a-z0-9]{0,50}") {
+            let sut = TextNumberProvider::default();
+            let mut res: Vec<u32> = sut.numbers(&text).rev().collect();
+            res.reverse();
+            prop_assert_eq!(res, sut.numbers(&text).collect::<Vec<u32>>());
+        }
+
+        #[test]
+        fn finds_at_least_the_digits(text in "[a-z0-9\n]{0,200}") {
+            let res = TextNumberProvider::default().extract_numbers(&text);
+            let digits = DefaultNumberProvider {}.extract_numbers(&text);
+            prop_assert_eq!(res.len(), digits.len());
+            for (line, line_digits) in res.iter().zip(digits.iter()) {
+                prop_assert!(line.len() >= line_digits.len());
+            }
+        }
+    }
 }
@@ -0,0 +1,243 @@
+use std::str::CharIndices;
+
+use super::number_provider::NumberProvider;
+
+/// Recognises every Unicode decimal digit (general category `Nd`), such as
+/// full-width `３`, Arabic-Indic `٣` or Devanagari `३`, not only `0` to `9`.
+pub struct UnicodeNumberProvider {}
+
+/// The zero of every run of ten decimal digits, sorted, as of Unicode 15.0.
+const DIGIT_ZEROS: [char; 68] = [
+    '\u{30}',
+    '\u{660}',
+    '\u{6F0}',
+    '\u{7C0}',
+    '\u{966}',
+    '\u{9E6}',
+    '\u{A66}',
+    '\u{AE6}',
+    '\u{B66}',
+    '\u{BE6}',
+    '\u{C66}',
+    '\u{CE6}',
+    '\u{D66}',
+    '\u{DE6}',
+    '\u{E50}',
+    '\u{ED0}',
+    '\u{F20}',
+    '\u{1040}',
+    '\u{1090}',
+    '\u{17E0}',
+    '\u{1810}',
+    '\u{1946}',
+    '\u{19D0}',
+    '\u{1A80}',
+    '\u{1A90}',
+    '\u{1B50}',
+    '\u{1BB0}',
+    '\u{1C40}',
+    '\u{1C50}',
+    '\u{A620}',
+    '\u{A8D0}',
+    '\u{A900}',
+    '\u{A9D0}',
+    '\u{A9F0}',
+    '\u{AA50}',
+    '\u{ABF0}',
+    '\u{FF10}',
+    '\u{104A0}',
+    '\u{10D30}',
+    '\u{11066}',
+    '\u{110F0}',
+    '\u{11136}',
+    '\u{111D0}',
+    '\u{112F0}',
+    '\u{11450}',
+    '\u{114D0}',
+    '\u{11650}',
+    '\u{116C0}',
+    '\u{11730}',
+    '\u{118E0}',
+    '\u{11950}',
+    '\u{11C50}',
+    '\u{11D50}',
+    '\u{11DA0}',
+    '\u{11F50}',
+    '\u{16A60}',
+    '\u{16AC0}',
+    '\u{16B50}',
+    '\u{1D7CE}',
+    '\u{1D7D8}',
+    '\u{1D7E2}',
+    '\u{1D7EC}',
+    '\u{1D7F6}',
+    '\u{1E140}',
+    '\u{1E2F0}',
+    '\u{1E4F0}',
+    '\u{1E950}',
+    '\u{1FBF0}',
+];
+
+/// The value of `c` if it is a decimal digit in any script.
+pub fn unicode_digit(c: char) -> Option<u32> {
+    if c.is_ascii() {
+        return c.to_digit(10);
+    }
+
+    let zero = match DIGIT_ZEROS.binary_search(&c) {
+        Ok(i) => DIGIT_ZEROS[i],
+        Err(0) => return None,
+        Err(i) => DIGIT_ZEROS[i - 1],
+    };
+    let value = c as u32 - zero as u32;
+    if value < 10 {
+        return Some(value);
+    }
+    return None;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DigitMatch {
+    pub value: u32,
+    /// Offset in chars from the start of the line.
+    pub char_offset: usize,
+    /// Offset in bytes from the start of the line.
+    pub byte_offset: usize,
+}
+
+/// The digits of a line together with their position.
+pub struct UnicodeDigitMatches<'a> {
+    chars: CharIndices<'a>,
+    char_offset: usize,
+}
+
+impl<'a> Iterator for UnicodeDigitMatches<'a> {
+    type Item = DigitMatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (byte_offset, c) in self.chars.by_ref() {
+            let char_offset = self.char_offset;
+            self.char_offset += 1;
+
+            if let Some(value) = unicode_digit(c) {
+                return Some(DigitMatch {
+                    value,
+                    char_offset,
+                    byte_offset,
+                });
+            }
+        }
+        return None;
+    }
+}
+
+impl UnicodeNumberProvider {
+    pub fn digit_matches<'a>(&self, line: &'a str) -> UnicodeDigitMatches<'a> {
+        return UnicodeDigitMatches {
+            chars: line.char_indices(),
+            char_offset: 0,
+        };
+    }
+}
+
+/// The digits of a line, can be walked from both ends.
+pub struct UnicodeDigits<'a> {
+    chars: CharIndices<'a>,
+}
+
+impl<'a> Iterator for UnicodeDigits<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.chars.by_ref().find_map(|(_, c)| unicode_digit(c));
+    }
+}
+
+impl<'a> DoubleEndedIterator for UnicodeDigits<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        return self
+            .chars
+            .by_ref()
+            .rev()
+            .find_map(|(_, c)| unicode_digit(c));
+    }
+}
+
+impl NumberProvider for UnicodeNumberProvider {
+    type Numbers<'a> = UnicodeDigits<'a>;
+
+    fn numbers<'a>(&'a self, line: &'a str) -> Self::Numbers<'a> {
+        return UnicodeDigits {
+            chars: line.char_indices(),
+        };
+    }
+
+    fn last(&self, line: &str) -> Option<u32> {
+        return self.numbers(line).next_back();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::one::default_number_provider::DefaultNumberProvider;
+    use proptest::prelude::*;
+
+    #[test]
+    fn digits_of_other_scripts() {
+        assert_eq!(unicode_digit('7'), Some(7));
+        assert_eq!(unicode_digit('３'), Some(3));
+        assert_eq!(unicode_digit('٣'), Some(3));
+        assert_eq!(unicode_digit('३'), Some(3));
+        assert_eq!(unicode_digit('๙'), Some(9));
+        assert_eq!(unicode_digit('𝟘'), Some(0));
+    }
+
+    #[test]
+    fn not_digits() {
+        assert_eq!(unicode_digit('a'), None);
+        assert_eq!(unicode_digit('½'), None);
+        assert_eq!(unicode_digit('Ⅻ'), None);
+        assert_eq!(unicode_digit('²'), None);
+        assert_eq!(unicode_digit('\u{a0}'), None);
+    }
+
+    #[test]
+    fn extract_mixed_scripts() {
+        let sut = UnicodeNumberProvider {};
+        let res = sut.extract_numbers("a１b٢c\n३x4");
+        assert_eq!(res, vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(sut.first("xx٥yy９"), Some(5));
+        assert_eq!(sut.last("xx٥yy９"), Some(9));
+    }
+
+    #[test]
+    fn offsets_of_multi_byte_input() {
+        let sut = UnicodeNumberProvider {};
+        let res: Vec<DigitMatch> = sut.digit_matches("é３x٤").collect();
+        assert_eq!(
+            res,
+            vec![
+                DigitMatch {
+                    value: 3,
+                    char_offset: 1,
+                    byte_offset: 2,
+                },
+                DigitMatch {
+                    value: 4,
+                    char_offset: 3,
+                    byte_offset: 6,
+                },
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_default_on_ascii(text in "[ -~\n]{0,200}") {
+            let res = UnicodeNumberProvider {}.extract_numbers(&text);
+            prop_assert_eq!(res, DefaultNumberProvider {}.extract_numbers(&text));
+        }
+    }
+}
@@ -1,146 +1,136 @@
-pub fn find_valid_numbers(text: &str) -> Vec<u32> {
-    let lines: Vec<Vec<char>> = text.lines().map(|x| x.chars().collect()).collect();
-    let mut res: Vec<u32> = vec![];
-    for (i, line) in lines.iter().enumerate() {
-        let line_before = if i == 0 { None } else { Some(&lines[i - 1]) };
-        let line_after = if i == lines.len() - 1 {
-            None
-        } else {
-            Some(&lines[i + 1])
-        };
+use rules::AdjacencyRules;
+use schematic::Schematic;
+use util::dsu::grid_components;
+use util::geom::{BoundingBox, Direction, Point, Vector};
+use util::interval::Interval;
 
-        res.extend(find_valid_numbers_on_line(line, line_before, line_after));
-    }
-    return res;
-}
+use crate::answer::AnswerError;
+
+pub mod rules;
+pub mod schematic;
 
-fn find_valid_numbers_on_line(
-    line: &Vec<char>,
-    before: Option<&Vec<char>>,
-    after: Option<&Vec<char>>,
-) -> Vec<u32> {
-    let number_iterator = NumberIter::new(line);
-    return number_iterator
-        .filter(|x| x.is_valid(line, before, after))
-        .map(|x| x.nr)
+/// Digits joined by steps in these directions belong to the same number.
+const DIGIT_NEIGHBOURS: [Direction; 2] = [Direction::East, Direction::West];
+
+pub fn find_valid_numbers(text: &str, rules: &AdjacencyRules) -> Result<Vec<u32>, AnswerError> {
+    let schematic = Schematic::new(text);
+    return number_cells(&schematic)
+        .iter()
+        .filter(|x| touches_symbol(&schematic, x, rules))
+        .map(|x| read_number(&schematic, x))
         .collect();
 }
 
-struct NumberIter<'a> {
-    current: usize,
-    str: &'a Vec<char>,
+/// Every number of every line, whether it touches a symbol or not.
+pub fn find_numbers(text: &str) -> Result<Vec<Vec<FoundNumber>>, AnswerError> {
+    let schematic = Schematic::new(text);
+    let mut res: Vec<Vec<FoundNumber>> = vec![vec![]; schematic.height()];
+    for cells in number_cells(&schematic) {
+        let row = cells[0].y as usize;
+        res[row].push(FoundNumber {
+            nr: read_number(&schematic, &cells)?,
+            start_index: cells[0].x as usize,
+            end_index: cells[cells.len() - 1].x as usize,
+        });
+    }
+    return Ok(res);
 }
 
-impl<'a> NumberIter<'a> {
-    fn new(str: &'a Vec<char>) -> NumberIter<'a> {
-        return NumberIter { current: 0, str };
-    }
+/// The cells of every number in reading order, each number being a connected
+/// group of digits.
+fn number_cells(schematic: &Schematic) -> Vec<Vec<Point>> {
+    return grid_components(schematic.rows(), |x| x.is_ascii_digit(), &DIGIT_NEIGHBOURS);
 }
 
-impl<'a> Iterator for NumberIter<'a> {
-    type Item = FoundNumber;
+/// Reads the digits of `cells` in order, an error when they do not fit a
+/// `u32`.
+fn read_number(schematic: &Schematic, cells: &[Point]) -> Result<u32, AnswerError> {
+    return cells
+        .iter()
+        .map(|x| schematic.at(*x).unwrap())
+        .collect::<String>()
+        .parse::<u32>()
+        .map_err(|_| AnswerError::NumberTooLarge);
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut number: Vec<char> = vec![];
-        let mut start_pos: Option<usize> = None;
+/// Lines may differ in length, cells past the end of a line are empty.
+fn touches_symbol(schematic: &Schematic, cells: &[Point], rules: &AdjacencyRules) -> bool {
+    // Every cell of the schematic, wrapped or not, lies less than its width
+    // and height away, so a larger distance reaches nothing more.
+    let bounds = schematic.bounds();
+    let distance = i64::try_from(rules.distance).unwrap_or(i64::MAX);
+    let reach = Vector::new(
+        distance.min(bounds.width() - 1),
+        distance.min(bounds.height() - 1),
+    );
+    for cell in cells {
+        let around = BoundingBox {
+            min: *cell - reach,
+            max: *cell + reach,
+        };
+        // The cells of the number itself hold digits, which are never symbols.
+        for point in around.points() {
+            if !rules.is_within(point - *cell) {
+                continue;
+            }
 
-        for i in self.current..self.str.len() {
-            if self.str[i].is_ascii_digit() {
-                if start_pos.is_none() {
-                    start_pos = Some(i);
-                }
-                number.push(self.str[i]);
+            let point = if rules.wrap {
+                bounds.wrap(point)
             } else {
-                if start_pos.is_some() {
-                    break;
-                }
+                point
+            };
+            if schematic.at(point).is_some_and(|x| rules.is_symbol(x)) {
+                return true;
             }
         }
-
-        if start_pos.is_none() {
-            return None;
-        }
-
-        self.current = start_pos.unwrap() + number.len();
-
-        return Some(FoundNumber {
-            nr: number
-                .clone()
-                .into_iter()
-                .collect::<String>()
-                .parse::<u32>()
-                .unwrap(),
-            start_index: start_pos.unwrap(),
-            end_index: (start_pos.unwrap() + number.len() - 1),
-        });
     }
+
+    return false;
 }
 
-#[derive(PartialEq, Debug)]
-struct FoundNumber {
-    nr: u32,
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FoundNumber {
+    pub nr: u32,
     /// Inclusive
-    start_index: usize,
+    pub start_index: usize,
     /// Inclusive
-    end_index: usize,
+    pub end_index: usize,
 }
 
 impl FoundNumber {
-    fn is_valid(
-        &self,
-        line: &Vec<char>,
-        before: Option<&Vec<char>>,
-        after: Option<&Vec<char>>,
-    ) -> bool {
-        let mut min_index = self.start_index;
-        let mut max_index = self.end_index;
-        if self.start_index != 0 {
-            min_index -= 1;
-            let line_before = line[self.start_index - 1];
-
-            if line_before != '.' {
-                return true;
-            }
-        }
-
-        if self.end_index != line.len() - 1 {
-            max_index += 1;
-            let line_after = line[self.end_index + 1];
-
-            if line_after != '.' {
-                return true;
-            }
-        }
-
-        if before.is_some() {
-            let before_some = before.unwrap();
-            for i in min_index..max_index + 1 {
-                let before_char = before_some[i];
-
-                if !before_char.is_digit(10) && before_char != '.' {
-                    return true;
-                }
-            }
-        }
-        if after.is_some() {
-            let after_some = after.unwrap();
-            for i in min_index..max_index + 1 {
-                let after_char = after_some[i];
+    /// The columns the digits of the number occupy. Adjacency is checked
+    /// per cell of the number, so this only describes where it lies.
+    pub fn span(&self) -> Interval<usize> {
+        return Interval::inclusive(self.start_index, self.end_index)
+            .expect("a column of a line is below usize::MAX");
+    }
 
-                if !after_char.is_digit(10) && after_char != '.' {
-                    return true;
-                }
-            }
-        }
+    /// The cells the digits occupy when the number lies on `row`.
+    pub fn cells(&self, row: usize) -> Vec<Point> {
+        return self
+            .span()
+            .range()
+            .map(|x| Point::new(x as i64, row as i64))
+            .collect();
+    }
 
-        return false;
+    /// Whether the number on `row` touches a symbol.
+    pub fn is_valid(&self, schematic: &Schematic, row: usize, rules: &AdjacencyRules) -> bool {
+        return touches_symbol(schematic, &self.cells(row), rules);
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
+    use super::rules::{Adjacency, Symbols};
     use super::*;
 
+    fn grid(lines: &[&str]) -> Schematic {
+        return Schematic::new(&lines.join("\n"));
+    }
+
     #[test]
     fn find_valid_numbers_finds_all_numbers() {
         let challenge = "467..114..
@@ -153,83 +143,224 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        let res = find_valid_numbers(challenge);
-        assert_eq!(
-            res,
-            vec![467, 35, 633, 617, 592, 755, 664, 598]
-        );
+        let res = find_valid_numbers(challenge, &AdjacencyRules::default()).unwrap();
+        assert_eq!(res, vec![467, 35, 633, 617, 592, 755, 664, 598]);
     }
 
     #[test]
     fn find_numbers_on_line_finds_2() {
-        let line_b = &"...+......".chars().collect();
-        let line_a = &".565.114+.".chars().collect();
-        let line_c = &"..........".chars().collect();
-        let res = find_valid_numbers_on_line(line_a, Some(line_b), Some(line_c));
-        assert_eq!(
-            res,
-            vec![565, 114]
-        );
+        let lines = "...+......\n.565.114+.\n..........";
+        let res = find_valid_numbers(lines, &AdjacencyRules::default()).unwrap();
+        assert_eq!(res, vec![565, 114]);
     }
 
     #[test]
     fn is_valid() {
-        let line_b = &"..........".chars().collect();
-        let line_a = &".....114+.".chars().collect();
-        let line_c = &"..........".chars().collect();
+        let lines = grid(&["..........", ".....114+.", ".........."]);
         let found_number = FoundNumber {
             nr: 114,
             start_index: 5,
             end_index: 7,
         };
-        let res = found_number.is_valid(line_a, Some(line_b), Some(line_c));
-        assert_eq!(
-            res,
-            true
-        );
+        let res = found_number.is_valid(&lines, 1, &AdjacencyRules::default());
+        assert_eq!(res, true);
     }
 
     #[test]
     fn is_valid_misses_invalid() {
-        let line_b = &"..........".chars().collect();
-        let line_a = &".....114.+".chars().collect();
-        let line_c = &"..........".chars().collect();
+        let lines = grid(&["..........", ".....114.+", ".........."]);
         let found_number = FoundNumber {
             nr: 114,
             start_index: 5,
             end_index: 7,
         };
-        let res = found_number.is_valid(line_a, Some(line_b), Some(line_c));
+        let res = found_number.is_valid(&lines, 1, &AdjacencyRules::default());
+        assert_eq!(res, false);
+    }
+
+    #[test]
+    fn number_too_large_is_an_error() {
+        let res = find_valid_numbers("99999999999*", &AdjacencyRules::default());
+        assert_eq!(res, Err(AnswerError::NumberTooLarge));
+        assert_eq!(find_numbers("1\n4294967296"), Err(AnswerError::NumberTooLarge));
+        assert_eq!(find_numbers("4294967295").unwrap()[0][0].nr, u32::MAX);
+    }
+
+    #[test]
+    fn huge_distance_reaches_the_whole_schematic() {
+        let text = "1.........\n..........\n.........*";
+        for wrap in [false, true] {
+            let rules = AdjacencyRules {
+                distance: usize::MAX,
+                wrap,
+                ..Default::default()
+            };
+            assert_eq!(find_valid_numbers(text, &rules).unwrap(), vec![1]);
+        }
+    }
+
+    #[test]
+    fn find_numbers_in_line() {
+        let res = find_numbers("467..114..\n\n.5").unwrap();
         assert_eq!(
-            res,
-            false
+            res[0],
+            vec![
+                FoundNumber {
+                    nr: 467,
+                    start_index: 0,
+                    end_index: 2
+                },
+                FoundNumber {
+                    nr: 114,
+                    start_index: 5,
+                    end_index: 7
+                }
+            ]
         );
+        assert_eq!(res[1], vec![]);
+        assert_eq!(res[2][0].cells(2), vec![Point::new(1, 2)]);
     }
 
     #[test]
-    fn find_next_number_found() {
-        let mut iterator = NumberIter {
-            current: 4,
-            str: &"467..114..".chars().collect(),
+    fn digits_below_each_other_are_separate_numbers() {
+        let res = find_valid_numbers("1*\n2.", &AdjacencyRules::default()).unwrap();
+        assert_eq!(res, vec![1, 2]);
+    }
+
+    #[test]
+    fn span_covers_digits() {
+        let res = find_numbers("467..114..").unwrap();
+        assert_eq!(res[0][0].span(), Interval::new(0, 3));
+        assert_eq!(res[0][1].span().range(), 5..8);
+    }
+
+    #[test]
+    fn orthogonal_ignores_diagonal_symbol() {
+        let text = "*....\n.12..\n.....";
+        let rules = AdjacencyRules {
+            adjacency: Adjacency::Orthogonal,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![12]
+        );
+        assert_eq!(find_valid_numbers(text, &rules).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn distance_two_reaches_further() {
+        let text = "....*\n.12..\n.....";
+        let rules = AdjacencyRules {
+            distance: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![]
+        );
+        assert_eq!(find_valid_numbers(text, &rules).unwrap(), vec![12]);
+    }
+
+    #[test]
+    fn wrap_around_edges() {
+        let text = "12...\n.....\n....#";
+        let rules = AdjacencyRules {
+            wrap: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![]
+        );
+        assert_eq!(find_valid_numbers(text, &rules).unwrap(), vec![12]);
+    }
+
+    #[test]
+    fn configured_symbols_and_empty() {
+        let text = "12#\n...\n3 *";
+        let rules = AdjacencyRules {
+            empty: vec!['.', ' '],
+            symbols: Symbols::Only(vec!['*']),
+            ..Default::default()
         };
-        let res = iterator.next();
         assert_eq!(
-            res,
-            Some(FoundNumber {
-                nr: 114,
-                start_index: 5,
-                end_index: 7
-            })
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![12, 3]
+        );
+        assert_eq!(find_valid_numbers(text, &rules).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn find_numbers_per_line() {
+        let res = find_numbers("467..114..\n...*......\n..35..633.").unwrap();
+        assert_eq!(res.len(), 3);
+        assert_eq!(
+            res[0].iter().map(|x| x.nr).collect::<Vec<u32>>(),
+            vec![467, 114]
+        );
+        assert_eq!(res[1], vec![]);
+        assert_eq!(
+            res[2][1],
+            FoundNumber {
+                nr: 633,
+                start_index: 6,
+                end_index: 8
+            }
+        );
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(
+            find_valid_numbers("", &AdjacencyRules::default()).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let text = "\n12*\n\n\n.5.\n";
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![12]
+        );
+    }
+
+    #[test]
+    fn shorter_neighbouring_lines() {
+        let text = "..\n....123\n...*\n.";
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![123]
+        );
+    }
+
+    #[test]
+    fn longer_neighbouring_lines() {
+        let text = "..........#\n.........12\n...";
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![12]
+        );
+    }
+
+    #[test]
+    fn missing_cells_are_not_symbols() {
+        let text = "...\n..7....\n.";
+        assert_eq!(
+            find_valid_numbers(text, &AdjacencyRules::default()).unwrap(),
+            vec![]
         );
     }
 
     #[test]
-    fn find_next_number_notfound() {
-        let mut iterator = NumberIter {
-            current: 8,
-            str: &"467..114..".chars().collect(),
+    fn wrap_uses_widest_line() {
+        let rules = AdjacencyRules {
+            wrap: true,
+            ..Default::default()
         };
-        let res = iterator.next();
-        assert_eq!(res, None);
+        assert_eq!(find_valid_numbers("5\n\n....#", &rules).unwrap(), vec![5]);
+        assert_eq!(find_valid_numbers("5\n\n...#.", &rules).unwrap(), vec![]);
     }
 }
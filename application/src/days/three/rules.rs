@@ -0,0 +1,113 @@
+use util::geom::Vector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Adjacency {
+    /// Only cells reachable by horizontal and vertical steps count as
+    /// neighbours, so `distance` is a Manhattan distance.
+    Orthogonal,
+    /// Diagonal steps count as well, so `distance` is a Chebyshev distance.
+    Diagonal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Symbols {
+    /// Every character that is neither a digit nor empty is a symbol.
+    AnyNonEmpty,
+    /// Only the listed characters are symbols.
+    Only(Vec<char>),
+}
+
+/// Decides which cells around a number are checked for symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AdjacencyRules {
+    pub adjacency: Adjacency,
+    pub distance: usize,
+    pub empty: Vec<char>,
+    pub symbols: Symbols,
+    /// Treat the schematic as a torus, so the first and last rows and
+    /// columns are neighbours.
+    pub wrap: bool,
+}
+
+impl Default for AdjacencyRules {
+    fn default() -> Self {
+        return AdjacencyRules {
+            adjacency: Adjacency::Diagonal,
+            distance: 1,
+            empty: vec!['.'],
+            symbols: Symbols::AnyNonEmpty,
+            wrap: false,
+        };
+    }
+}
+
+impl AdjacencyRules {
+    pub fn is_symbol(&self, c: char) -> bool {
+        if c.is_ascii_digit() || self.empty.contains(&c) {
+            return false;
+        }
+        return match &self.symbols {
+            Symbols::AnyNonEmpty => true,
+            Symbols::Only(symbols) => symbols.contains(&c),
+        };
+    }
+
+    /// Whether a cell `offset` away from the nearest digit of a number is
+    /// close enough to count.
+    pub fn is_within(&self, offset: Vector) -> bool {
+        let length = match self.adjacency {
+            Adjacency::Orthogonal => offset.manhattan_length(),
+            Adjacency::Diagonal => offset.chebyshev_length(),
+        };
+        return usize::try_from(length).is_ok_and(|x| x <= self.distance);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_symbols() {
+        let sut = AdjacencyRules::default();
+        assert!(sut.is_symbol('*'));
+        assert!(sut.is_symbol('#'));
+        assert!(!sut.is_symbol('.'));
+        assert!(!sut.is_symbol('7'));
+    }
+
+    #[test]
+    fn only_listed_symbols() {
+        let sut = AdjacencyRules {
+            empty: vec!['.', ' '],
+            symbols: Symbols::Only(vec!['*']),
+            ..Default::default()
+        };
+        assert!(sut.is_symbol('*'));
+        assert!(!sut.is_symbol('#'));
+        assert!(!sut.is_symbol(' '));
+    }
+
+    #[test]
+    fn within_distance() {
+        let diagonal = AdjacencyRules::default();
+        let orthogonal = AdjacencyRules {
+            adjacency: Adjacency::Orthogonal,
+            distance: 2,
+            ..Default::default()
+        };
+        assert!(diagonal.is_within(Vector::new(1, 1)));
+        assert!(!diagonal.is_within(Vector::new(0, -2)));
+        assert!(orthogonal.is_within(Vector::new(1, 1)));
+        assert!(orthogonal.is_within(Vector::new(-2, 0)));
+        assert!(!orthogonal.is_within(Vector::new(1, -2)));
+        let unlimited = AdjacencyRules {
+            distance: usize::MAX,
+            ..Default::default()
+        };
+        assert!(unlimited.is_within(Vector::new(i64::MAX, i64::MIN + 1)));
+    }
+}
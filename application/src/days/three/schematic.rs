@@ -0,0 +1,132 @@
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use util::geom::{BoundingBox, Point};
+
+/// An engine schematic as a grid of cells, rows may differ in length.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schematic {
+    rows: Vec<Vec<char>>,
+}
+
+impl Schematic {
+    /// Every line of the text is a row, `\r\n` line endings are accepted.
+    pub fn new(text: &str) -> Schematic {
+        return Schematic {
+            rows: text.lines().map(|x| x.chars().collect()).collect(),
+        };
+    }
+
+    pub fn rows(&self) -> &[Vec<char>] {
+        return &self.rows;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.rows.len();
+    }
+
+    /// Length of the widest row.
+    pub fn width(&self) -> usize {
+        return self.rows.iter().map(|x| x.len()).max().unwrap_or(0);
+    }
+
+    /// Cells outside the grid, including past the end of a shorter row, are `None`.
+    pub fn get(&self, row: usize, column: usize) -> Option<char> {
+        return self.rows.get(row)?.get(column).copied();
+    }
+
+    /// Like `get`, but for points that may lie left of or above the grid.
+    pub fn at(&self, point: Point) -> Option<char> {
+        let row = usize::try_from(point.y).ok()?;
+        let column = usize::try_from(point.x).ok()?;
+        return self.get(row, column);
+    }
+
+    /// Box from the top left cell to the bottom right corner of the widest row.
+    pub fn bounds(&self) -> BoundingBox {
+        return BoundingBox {
+            min: Point::ORIGIN,
+            max: Point::new(self.width() as i64 - 1, self.height() as i64 - 1),
+        };
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Ok(Schematic::new(s));
+    }
+}
+
+impl Display for Schematic {
+    /// Writes every row followed by a newline.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::three::{generate_schematic, SchematicConfig};
+    use proptest::prelude::*;
+
+    #[test]
+    fn points_outside_grid() {
+        let sut = Schematic::new("12\n3");
+        assert_eq!(sut.at(Point::new(0, 1)), Some('3'));
+        assert_eq!(sut.at(Point::new(1, 1)), None);
+        assert_eq!(sut.at(Point::new(-1, 0)), None);
+        assert_eq!(sut.bounds().max, Point::new(1, 1));
+    }
+
+    #[test]
+    fn parse_ragged_rows() {
+        let res: Schematic = "467..\n...*\r\n\n.6".parse().unwrap();
+        assert_eq!(res.height(), 4);
+        assert_eq!(res.width(), 5);
+        assert_eq!(res.get(1, 3), Some('*'));
+        assert_eq!(res.get(1, 4), None);
+        assert_eq!(res.get(2, 0), None);
+        assert_eq!(res.get(4, 0), None);
+    }
+
+    #[test]
+    fn canonical_format() {
+        let res: Schematic = "467..\r\n...*\n".parse().unwrap();
+        assert_eq!(res.to_string(), "467..\n...*\n");
+        assert_eq!(Schematic::new("").to_string(), "");
+    }
+
+    #[test]
+    fn blank_rows_round_trip() {
+        let res = Schematic::new("\n\n.\n");
+        assert_eq!(res.height(), 3);
+        assert_eq!(res.to_string().parse::<Schematic>().unwrap(), res);
+    }
+
+    proptest! {
+        #[test]
+        fn generated_schematic_round_trips(seed in any::<u64>(), size in 0usize..30) {
+            let text = generate_schematic(seed, &SchematicConfig::square(size)).unwrap();
+            let res: Schematic = text.parse().unwrap();
+            prop_assert_eq!(res.height(), size);
+            prop_assert_eq!(res.to_string().parse::<Schematic>().unwrap(), res.clone());
+            if size > 0 {
+                prop_assert_eq!(res.to_string(), text + "\n");
+            }
+        }
+
+        #[test]
+        fn any_text_round_trips(text in "[.#*0-9\n]{0,100}") {
+            let res = Schematic::new(&text);
+            prop_assert_eq!(Schematic::new(&res.to_string()), res);
+        }
+    }
+}
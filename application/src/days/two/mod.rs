@@ -1,26 +1,52 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use parser::{parse_line, parse_set, ParseError};
+
+use crate::answer::{Answer, AnswerError};
+
+pub mod parser;
+
 pub fn extract_possible_games(
     text: &str,
     max_red: i32,
     max_blue: i32,
     max_green: i32,
-) -> Vec<LineInfo> {
+) -> Result<Vec<LineInfo>, ParseError> {
     let mut possible_games = vec![];
-    for line in text.lines() {
-        let line_info = parse_line(line);
+    for line_info in parse_lines(text) {
+        let line_info = line_info?;
         if line_info.is_possible(max_red, max_blue, max_green) {
             possible_games.push(line_info);
         }
     }
-    return possible_games;
+    return Ok(possible_games);
+}
+
+pub fn extract_least_cubes(text: &str) -> Result<Vec<SetInfo>, ParseError> {
+    return parse_lines(text)
+        .map(|x| x.map(|line_info| line_info.least_cubes()))
+        .collect();
 }
 
-pub fn extract_least_cubes(text: &str) -> Vec<SetInfo> {
-    return text.lines().map(|x| parse_line(x).least_cubes()).collect();
+/// Parses every game, blank lines are skipped.
+pub fn parse_games(text: &str) -> Result<Vec<LineInfo>, ParseError> {
+    return parse_lines(text).collect();
 }
 
+fn parse_lines(text: &str) -> impl Iterator<Item = Result<LineInfo, ParseError>> + '_ {
+    return text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| parse_line(line).map_err(|x| x.on_line(i + 1)));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineInfo {
     pub nr: i32,
-    sets: Vec<SetInfo>,
+    pub sets: Vec<SetInfo>,
 }
 
 impl LineInfo {
@@ -56,64 +82,76 @@ impl LineInfo {
     }
 }
 
+impl Display for LineInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.nr)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            if i != 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", set)?;
+        }
+        return Ok(());
+    }
+}
+
+impl FromStr for LineInfo {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return parse_line(s);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SetInfo {
-    red_count: i32,
-    blue_count: i32,
-    green_count: i32,
+    pub red_count: i32,
+    pub blue_count: i32,
+    pub green_count: i32,
 }
 
 impl SetInfo {
-    pub fn power(&self) -> i32 {
-        return self.red_count * self.blue_count * self.green_count;
+    pub fn power(&self) -> Result<Answer, AnswerError> {
+        return Answer::product([self.red_count, self.blue_count, self.green_count]);
     }
 }
 
-fn parse_line(line: &str) -> LineInfo {
-    let mut draft_line_info = LineInfo {
-        nr: 0,
-        sets: vec![],
-    };
-
-    let res = line.chars().skip(5).take_while(|x| *x != ':');
-    let line_nr_str: String = res.into_iter().collect();
-
-    draft_line_info.nr = line_nr_str.parse().unwrap();
-    draft_line_info.sets = line
-        .split(": ")
-        .skip(1)
-        .last()
-        .unwrap()
-        .split("; ")
-        .map(|x| parse_subset(x))
-        .collect::<Vec<SetInfo>>();
-
-    return draft_line_info;
-}
+impl Display for SetInfo {
+    /// Writes the colours in red, green, blue order and leaves out colours
+    /// that were not drawn, an empty set is written as `0 red`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts = [
+            (self.red_count, "red"),
+            (self.green_count, "green"),
+            (self.blue_count, "blue"),
+        ];
+        let drawn: Vec<String> = counts
+            .iter()
+            .filter(|(count, _)| *count != 0)
+            .map(|(count, colour)| format!("{} {}", count, colour))
+            .collect();
 
-fn parse_subset(set: &str) -> SetInfo {
-    let mut draft_set_info = SetInfo {
-        red_count: 0,
-        blue_count: 0,
-        green_count: 0,
-    };
-    for color_text in set.split(", ") {
-        let mut split = color_text.split(" ");
-        let nr: i32 = split.next().unwrap().parse().unwrap();
-        let color = split.next().unwrap();
-        match color {
-            "blue" => draft_set_info.blue_count = nr,
-            "red" => draft_set_info.red_count = nr,
-            "green" => draft_set_info.green_count = nr,
-            _ => panic!("Unexpected color"),
+        if drawn.is_empty() {
+            return write!(f, "0 red");
         }
+        return write!(f, "{}", drawn.join(", "));
     }
+}
+
+impl FromStr for SetInfo {
+    type Err = ParseError;
 
-    return draft_set_info;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return parse_set(s);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::two::{generate_games, GameConfig};
+    use proptest::prelude::*;
 
     #[test]
     fn extract_single_possible() {
@@ -122,7 +160,8 @@ mod tests {
             12,
             14,
             13,
-        );
+        )
+        .unwrap();
         assert_eq!(res.len(), 1);
     }
 
@@ -133,7 +172,8 @@ mod tests {
             1,
             14,
             13,
-        );
+        )
+        .unwrap();
         assert_eq!(res.len(), 0);
     }
 
@@ -148,7 +188,8 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
             12,
             14,
             13,
-        );
+        )
+        .unwrap();
         assert_eq!(res.len(), 3);
         assert_eq!(res[0].nr, 1);
         assert_eq!(res[1].nr, 2);
@@ -157,7 +198,8 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
 
     #[test]
     fn extract_least_single() {
-        let res = extract_least_cubes("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let res =
+            extract_least_cubes("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
         assert_eq!(res.len(), 1);
         assert_eq!(res[0].red_count, 4);
         assert_eq!(res[0].green_count, 2);
@@ -172,7 +214,8 @@ Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
-        );
+        )
+        .unwrap();
         assert_eq!(res.len(), 5);
         check_game(
             &res[0],
@@ -215,6 +258,55 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
             },
         );
     }
+
+    #[test]
+    fn extract_reports_line_of_error() {
+        let res = extract_least_cubes("Game 1: 3 blue\nGame 2: 3 bleu").unwrap_err();
+        assert_eq!(res.line, 2);
+    }
+
+    #[test]
+    fn power_does_not_overflow() {
+        let set = SetInfo {
+            red_count: i32::MAX,
+            blue_count: i32::MAX,
+            green_count: 2,
+        };
+        assert_eq!(set.power().unwrap().value(), 2 * (i32::MAX as u128).pow(2));
+    }
+
+    #[test]
+    fn line_info_from_str_and_back() {
+        let res: LineInfo = "game 3 : 2 Reds,1 blue;".parse().unwrap();
+        assert_eq!(res.to_string(), "Game 3: 2 red, 1 blue");
+        assert_eq!(res.to_string().parse::<LineInfo>().unwrap(), res);
+    }
+
+    #[test]
+    fn set_info_from_str_and_back() {
+        let res: SetInfo = "3 blue, 4 red".parse().unwrap();
+        assert_eq!(res.to_string(), "4 red, 3 blue");
+        assert_eq!(res.to_string().parse::<SetInfo>().unwrap(), res);
+
+        let empty: SetInfo = "0 blue".parse().unwrap();
+        assert_eq!(empty.to_string().parse::<SetInfo>().unwrap(), empty);
+    }
+
+    proptest! {
+        #[test]
+        fn generated_games_round_trip(seed in any::<u64>()) {
+            let text = generate_games(seed, 10, &GameConfig::default()).unwrap();
+            for line in text.lines() {
+                let res: LineInfo = line.parse().unwrap();
+                let canonical = res.to_string();
+                prop_assert_eq!(canonical.parse::<LineInfo>().unwrap(), res.clone());
+                for set in &res.sets {
+                    prop_assert_eq!(&set.to_string().parse::<SetInfo>().unwrap(), set);
+                }
+            }
+        }
+    }
+
     fn check_game(actual: &SetInfo, expected: SetInfo) {
         assert_eq!(actual.red_count, expected.red_count);
         assert_eq!(actual.blue_count, expected.blue_count);
@@ -0,0 +1,336 @@
+use std::fmt::Display;
+
+use util::scan::{self, ScanError, ScanErrorKind, Scanner};
+
+use super::{LineInfo, SetInfo};
+
+/// Byte range of a token within its line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// Inclusive
+    pub start: usize,
+    /// Exclusive
+    pub end: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ParseErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedToken {
+        expected: &'static str,
+        found: String,
+    },
+    UnexpectedEnd {
+        expected: &'static str,
+    },
+    NumberTooLarge,
+    UnknownColour(String),
+    DuplicateColour(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParseError {
+    /// 1-based line number within the parsed text.
+    pub line: usize,
+    /// 1-based column of the start of `span`, counting chars.
+    pub column: usize,
+    pub span: Span,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn on_line(self, line: usize) -> ParseError {
+        return ParseError { line, ..self };
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        return match &self.kind {
+            ParseErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            ParseErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {}, found '{}'", expected, found)
+            }
+            ParseErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "expected {}, found end of line", expected)
+            }
+            ParseErrorKind::NumberTooLarge => write!(f, "number is too large"),
+            ParseErrorKind::UnknownColour(colour) => write!(f, "unknown colour '{}'", colour),
+            ParseErrorKind::DuplicateColour(colour) => {
+                write!(f, "colour '{}' appears twice in one set", colour)
+            }
+        };
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<scan::Span> for Span {
+    fn from(value: scan::Span) -> Self {
+        return Span {
+            start: value.start,
+            end: value.end,
+        };
+    }
+}
+
+fn error(scanner: &Scanner, span: scan::Span, kind: ParseErrorKind) -> ParseError {
+    return ParseError {
+        line: 1,
+        column: scanner.position(span.start).column,
+        span: span.into(),
+        kind,
+    };
+}
+
+/// Characters that may start a token of a game record.
+fn is_token_start(c: char) -> bool {
+    return c.is_ascii_digit() || c.is_alphabetic() || ":;,".contains(c);
+}
+
+/// Reports a scanner error as a failure to find `expected`.
+fn scan_error(scan_error: ScanError, expected: &'static str) -> ParseError {
+    let kind = match scan_error.kind {
+        ScanErrorKind::Unexpected { found, .. } => {
+            let c = found.chars().next().unwrap();
+            if is_token_start(c) {
+                ParseErrorKind::UnexpectedToken { expected, found }
+            } else {
+                ParseErrorKind::UnexpectedCharacter(c)
+            }
+        }
+        ScanErrorKind::UnexpectedEnd { .. } => ParseErrorKind::UnexpectedEnd { expected },
+        ScanErrorKind::NumberOutOfRange => ParseErrorKind::NumberTooLarge,
+    };
+    return ParseError {
+        line: 1,
+        column: scan_error.position.column,
+        span: scan_error.span.into(),
+        kind,
+    };
+}
+
+fn number(scanner: &mut Scanner) -> Result<i32, ParseError> {
+    return scanner
+        .unsigned()
+        .map(|x| x.0)
+        .map_err(|x| scan_error(x, "a number"));
+}
+
+fn end(scanner: &mut Scanner, expected: &'static str) -> Result<(), ParseError> {
+    return scanner.end().map_err(|x| scan_error(x, expected));
+}
+
+fn line(scanner: &mut Scanner) -> Result<LineInfo, ParseError> {
+    let game = scanner.word().map_err(|x| scan_error(x, "'Game'"))?;
+    if !game.text.eq_ignore_ascii_case("game") {
+        return Err(error(
+            scanner,
+            game.span,
+            ParseErrorKind::UnexpectedToken {
+                expected: "'Game'",
+                found: game.text.to_string(),
+            },
+        ));
+    }
+
+    let nr = number(scanner)?;
+    scanner.literal(":").map_err(|x| scan_error(x, "':'"))?;
+
+    let sets = scanner.separated(";", |x| !x.is_at_end(), set)?;
+    end(scanner, "';' or end of line")?;
+
+    return Ok(LineInfo { nr, sets });
+}
+
+fn set(scanner: &mut Scanner) -> Result<SetInfo, ParseError> {
+    let mut set = SetInfo {
+        red_count: 0,
+        blue_count: 0,
+        green_count: 0,
+    };
+    let mut seen = [false; 3];
+
+    let starts_draw = |x: &Scanner| x.peek().is_some_and(|c| c.is_ascii_digit());
+    scanner.separated(",", starts_draw, |scanner| {
+        let nr = number(scanner)?;
+        let colour = scanner.word().map_err(|x| scan_error(x, "a colour"))?;
+        let Some(index) = colour_index(colour.text) else {
+            return Err(error(
+                scanner,
+                colour.span,
+                ParseErrorKind::UnknownColour(colour.text.to_string()),
+            ));
+        };
+        let count = match index {
+            0 => &mut set.red_count,
+            1 => &mut set.green_count,
+            _ => &mut set.blue_count,
+        };
+        if seen[index] {
+            return Err(error(
+                scanner,
+                colour.span,
+                ParseErrorKind::DuplicateColour(colour.text.to_string()),
+            ));
+        }
+        seen[index] = true;
+        *count = nr;
+        return Ok(());
+    })?;
+
+    return Ok(set);
+}
+
+/// Index of a colour among red, green and blue, in any case and singular or
+/// plural. `None` for any other colour.
+pub fn colour_index(name: &str) -> Option<usize> {
+    let name = name.to_ascii_lowercase();
+    return match name.strip_suffix('s').unwrap_or(&name) {
+        "red" => Some(0),
+        "green" => Some(1),
+        "blue" => Some(2),
+        _ => None,
+    };
+}
+
+/// Parses a single game record such as `Game 1: 3 blue, 4 red; 2 green`.
+///
+/// Whitespace between tokens is ignored, colours may be singular or plural
+/// and a trailing `,` or `;` is accepted.
+pub fn parse_line(text: &str) -> Result<LineInfo, ParseError> {
+    return line(&mut Scanner::new(text));
+}
+
+/// Parses the draws of a single set such as `3 blue, 4 red`.
+pub fn parse_set(text: &str) -> Result<SetInfo, ParseError> {
+    let mut scanner = Scanner::new(text);
+    let res = set(&mut scanner)?;
+    end(&mut scanner, "',' or end of set")?;
+    return Ok(res);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(red_count: i32, green_count: i32, blue_count: i32) -> SetInfo {
+        return SetInfo {
+            red_count,
+            blue_count,
+            green_count,
+        };
+    }
+
+    #[test]
+    fn parse_canonical() {
+        let res = parse_line("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        assert_eq!(res.nr, 1);
+        assert_eq!(res.sets, vec![set(4, 0, 3), set(1, 2, 6), set(0, 2, 0)]);
+    }
+
+    #[test]
+    fn parse_whitespace_variations() {
+        let res = parse_line("  Game\t12 :3 blue ,\t4 red;1 red  ").unwrap();
+        assert_eq!(res.nr, 12);
+        assert_eq!(res.sets, vec![set(4, 0, 3), set(1, 0, 0)]);
+    }
+
+    #[test]
+    fn parse_singular_and_plural() {
+        let res = parse_line("Game 2: 1 reds, 2 greens, 1 Blue").unwrap();
+        assert_eq!(res.sets, vec![set(1, 2, 1)]);
+    }
+
+    #[test]
+    fn parse_trailing_separators() {
+        let res = parse_line("Game 3: 1 red, 2 green,; 4 blue;").unwrap();
+        assert_eq!(res.sets, vec![set(1, 2, 0), set(0, 0, 4)]);
+    }
+
+    #[test]
+    fn error_unknown_colour_span() {
+        let res = parse_line("Game 4: 1 red, 2 purple").unwrap_err();
+        assert_eq!(res.span, Span { start: 17, end: 23 });
+        assert_eq!(
+            res.kind,
+            ParseErrorKind::UnknownColour("purple".to_string())
+        );
+    }
+
+    #[test]
+    fn error_missing_colon() {
+        let res = parse_line("Game 4 1 red").unwrap_err();
+        assert_eq!(res.span, Span { start: 7, end: 8 });
+        assert_eq!(
+            res.kind,
+            ParseErrorKind::UnexpectedToken {
+                expected: "':'",
+                found: "1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn error_unexpected_end() {
+        let res = parse_line("Game 5: 3").unwrap_err();
+        assert_eq!(res.span, Span { start: 9, end: 9 });
+        assert_eq!(
+            res.kind,
+            ParseErrorKind::UnexpectedEnd {
+                expected: "a colour"
+            }
+        );
+    }
+
+    #[test]
+    fn error_unexpected_character() {
+        let res = parse_line("Game 6: 3 red & 2 blue").unwrap_err();
+        assert_eq!(res.span, Span { start: 14, end: 15 });
+        assert_eq!(res.kind, ParseErrorKind::UnexpectedCharacter('&'));
+    }
+
+    #[test]
+    fn error_duplicate_colour() {
+        let res = parse_line("Game 7: 3 red, 2 reds").unwrap_err();
+        assert_eq!(res.span, Span { start: 17, end: 21 });
+    }
+
+    #[test]
+    fn error_column_counts_chars() {
+        // The no-break space takes two bytes.
+        let res = parse_line("Game 9:\u{a0}3 red, 2 purple").unwrap_err();
+        assert_eq!(res.span, Span { start: 18, end: 24 });
+        assert_eq!(res.column, 18);
+        assert_eq!(
+            res.to_string(),
+            "line 1, column 18: unknown colour 'purple'"
+        );
+        let res = parse_line("Game 9:\u{a0}3 red & 2 blue").unwrap_err();
+        assert_eq!((res.span.start, res.column), (15, 15));
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = "Game 8: 4 red, 3 blue; 1 red, 2 green, 6 blue; 2 green";
+        let res = parse_line(text).unwrap();
+        assert_eq!(res.to_string(), text);
+        assert_eq!(parse_line(&res.to_string()).unwrap(), res);
+    }
+
+    #[test]
+    fn parse_single_set() {
+        assert_eq!(parse_set("3 blue, 4 red").unwrap(), set(4, 0, 3));
+        assert_eq!(parse_set(" 1 Green ,").unwrap(), set(0, 1, 0));
+    }
+
+    #[test]
+    fn parse_set_rejects_more_sets() {
+        let res = parse_set("3 blue; 4 red").unwrap_err();
+        assert_eq!(res.span, Span { start: 6, end: 7 });
+    }
+}
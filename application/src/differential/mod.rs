@@ -0,0 +1,72 @@
+//! Differential testing of the day solvers.
+//!
+//! Each day has a naive reference implementation that favours being
+//! obviously correct over being fast. The runner feeds generated inputs to
+//! both the reference and the real solver and reports the first input on
+//! which they disagree.
+
+use std::fmt::Debug;
+
+pub mod one;
+pub mod three;
+pub mod two;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Mismatch {
+    pub seed: u64,
+    pub input: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Runs `check` for `runs` consecutive seeds starting at `seed`, wrapping
+/// around after `u64::MAX`.
+///
+/// Returns the amount of inputs checked when every input matched.
+pub fn run(
+    check: fn(u64, usize) -> Result<(), Mismatch>,
+    seed: u64,
+    size: usize,
+    runs: usize,
+) -> Result<usize, Mismatch> {
+    for i in 0..runs as u64 {
+        check(seed.wrapping_add(i), size)?;
+    }
+    return Ok(runs);
+}
+
+fn compare<T: PartialEq + Debug>(
+    seed: u64,
+    input: &str,
+    expected: T,
+    actual: T,
+) -> Result<(), Mismatch> {
+    if expected == actual {
+        return Ok(());
+    }
+
+    return Err(Mismatch {
+        seed,
+        input: input.to_string(),
+        expected: format!("{:?}", expected),
+        actual: format!("{:?}", actual),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_wrap_around() {
+        let check = |seed: u64, _| {
+            if seed == 1 {
+                return compare(seed, "", 0, 1);
+            }
+            return Ok(());
+        };
+        assert_eq!(run(check, u64::MAX - 1, 0, 2).unwrap(), 2);
+        assert_eq!(run(check, u64::MAX, 0, 3).unwrap_err().seed, 1);
+    }
+}
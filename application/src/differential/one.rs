@@ -0,0 +1,77 @@
+use crate::days::one::{
+    calibrate, default_number_provider::DefaultNumberProvider,
+    text_number_provider::TextNumberProvider,
+};
+use crate::generate::one::generate_calibration;
+
+use super::{compare, Mismatch};
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Compares both parts of day one against the reference on a generated input.
+pub fn check(seed: u64, size: usize) -> Result<(), Mismatch> {
+    let input = generate_calibration(seed, size);
+
+    compare(
+        seed,
+        &input,
+        reference_calibrate(&input, false),
+        calibrate(&input, &DefaultNumberProvider {}).unwrap(),
+    )?;
+    return compare(
+        seed,
+        &input,
+        reference_calibrate(&input, true),
+        calibrate(&input, &TextNumberProvider::default()).unwrap(),
+    );
+}
+
+/// Tries every word at every position of every line.
+pub fn reference_calibrate(text: &str, include_words: bool) -> Vec<u32> {
+    let mut res = vec![];
+
+    for line in text.split('\n') {
+        let mut digits = vec![];
+        for (i, _) in line.char_indices() {
+            let rest = &line[i..];
+            if let Some(digit) = rest.chars().next().and_then(|x| x.to_digit(10)) {
+                digits.push(digit);
+                continue;
+            }
+            if !include_words {
+                continue;
+            }
+            for (value, word) in WORDS.iter().enumerate() {
+                if rest.starts_with(word) {
+                    digits.push(value as u32 + 1);
+                }
+            }
+        }
+
+        if let (Some(first), Some(last)) = (digits.first(), digits.last()) {
+            res.push(first * 10 + last);
+        }
+    }
+
+    return res;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_example() {
+        let res = reference_calibrate("two1nine\neightwothree\nxtwone3four", true);
+        assert_eq!(res, vec![29, 83, 24]);
+    }
+
+    #[test]
+    fn matches_reference() {
+        for seed in 0..20 {
+            check(seed, 50).unwrap();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::days::three::{find_valid_numbers, rules::AdjacencyRules};
+use crate::generate::three::{generate_schematic, SchematicConfig};
+
+use super::{compare, Mismatch};
+
+/// Compares day three against the reference on a generated `size` by `size`
+/// schematic.
+pub fn check(seed: u64, size: usize) -> Result<(), Mismatch> {
+    let input = generate_schematic(seed, &SchematicConfig::square(size)).unwrap();
+
+    return compare(
+        seed,
+        &input,
+        reference_valid_numbers(&input),
+        find_valid_numbers(&input, &AdjacencyRules::default()).unwrap(),
+    );
+}
+
+/// Checks every cell in the box around each number for a symbol.
+pub fn reference_valid_numbers(text: &str) -> Vec<u32> {
+    let grid: Vec<Vec<char>> = text.lines().map(|x| x.chars().collect()).collect();
+    let cell = |row: i64, col: i64| -> char {
+        if row < 0 || col < 0 {
+            return '.';
+        }
+        return grid
+            .get(row as usize)
+            .and_then(|x| x.get(col as usize))
+            .copied()
+            .unwrap_or('.');
+    };
+
+    let mut res = vec![];
+    for (row, line) in grid.iter().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if !line[col].is_ascii_digit() {
+                col += 1;
+                continue;
+            }
+
+            let start = col;
+            while col < line.len() && line[col].is_ascii_digit() {
+                col += 1;
+            }
+
+            let mut is_part = false;
+            for r in row as i64 - 1..=row as i64 + 1 {
+                for c in start as i64 - 1..=col as i64 {
+                    let x = cell(r, c);
+                    is_part |= !x.is_ascii_digit() && x != '.';
+                }
+            }
+
+            if is_part {
+                res.push(line[start..col].iter().collect::<String>().parse().unwrap());
+            }
+        }
+    }
+
+    return res;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_example() {
+        let res = reference_valid_numbers("467..114..\n...*......\n..35..633.\n......#...");
+        assert_eq!(res, vec![467, 35, 633]);
+    }
+
+    #[test]
+    fn matches_reference() {
+        for seed in 0..20 {
+            check(seed, 30).unwrap();
+        }
+    }
+}
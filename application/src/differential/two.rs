@@ -0,0 +1,84 @@
+use crate::days::two::{extract_least_cubes, extract_possible_games};
+use crate::generate::two::{generate_games, GameConfig};
+
+use super::{compare, Mismatch};
+
+/// Compares both parts of day two against the reference on a generated input.
+pub fn check(seed: u64, size: usize) -> Result<(), Mismatch> {
+    let input = generate_games(seed, size, &GameConfig::default()).unwrap();
+    let reference = reference_maxima(&input);
+
+    compare(
+        seed,
+        &input,
+        reference
+            .iter()
+            .filter(|(_, max)| max[0] <= 12 && max[1] <= 13 && max[2] <= 14)
+            .map(|(nr, _)| *nr)
+            .collect::<Vec<i32>>(),
+        extract_possible_games(&input, 12, 14, 13)
+            .unwrap()
+            .into_iter()
+            .map(|x| x.nr)
+            .collect(),
+    )?;
+    return compare(
+        seed,
+        &input,
+        reference
+            .iter()
+            .map(|(_, max)| max.iter().map(|x| *x as u128).product())
+            .collect::<Vec<u128>>(),
+        extract_least_cubes(&input)
+            .unwrap()
+            .into_iter()
+            .map(|x| x.power().unwrap().value())
+            .collect(),
+    );
+}
+
+/// Returns the game number and the highest red, green and blue count of
+/// every game, reading the line as a flat list of words.
+pub fn reference_maxima(text: &str) -> Vec<(i32, [i32; 3])> {
+    let mut res = vec![];
+
+    for line in text.lines() {
+        let words: Vec<&str> = line
+            .split(|x: char| x.is_whitespace() || x == ',' || x == ';' || x == ':')
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        let nr = words[1].parse().unwrap();
+        let mut max = [0; 3];
+        for pair in words[2..].chunks(2) {
+            let count: i32 = pair[0].parse().unwrap();
+            let colour = match pair[1] {
+                "red" => 0,
+                "green" => 1,
+                _ => 2,
+            };
+            max[colour] = max[colour].max(count);
+        }
+        res.push((nr, max));
+    }
+
+    return res;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reference_example() {
+        let res = reference_maxima("Game 7: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        assert_eq!(res, vec![(7, [4, 2, 6])]);
+    }
+
+    #[test]
+    fn matches_reference() {
+        for seed in 0..20 {
+            check(seed, 50).unwrap();
+        }
+    }
+}
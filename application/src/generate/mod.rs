@@ -0,0 +1,53 @@
+//! Seeded generators for puzzle inputs.
+//!
+//! Every generator takes a seed so a failing input can be reproduced by
+//! running the generator again with the same arguments.
+
+use std::fmt::Display;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+pub mod one;
+pub mod three;
+pub mod two;
+
+/// A generator setting that would leave nothing to choose from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// The named count must be at least 1.
+    NotPositive(&'static str),
+    /// The named chance must lie in `0..=1`.
+    InvalidChance { name: &'static str, value: f64 },
+    /// Day two does not read this colour.
+    UnknownColour(String),
+    /// Day two rejects a set naming the same colour twice.
+    DuplicateColour(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match self {
+            ConfigError::NotPositive(name) => write!(f, "{} must be at least 1", name),
+            ConfigError::InvalidChance { name, value } => {
+                write!(f, "{} must be between 0 and 1, got {}", name, value)
+            }
+            ConfigError::UnknownColour(colour) => write!(f, "unknown colour '{}'", colour),
+            ConfigError::DuplicateColour(colour) => {
+                write!(f, "colour '{}' is listed twice", colour)
+            }
+        };
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn check_chance(name: &'static str, value: f64) -> Result<(), ConfigError> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(ConfigError::InvalidChance { name, value });
+    }
+    return Ok(());
+}
+
+fn seeded_rng(seed: u64) -> StdRng {
+    return StdRng::seed_from_u64(seed);
+}
@@ -0,0 +1,94 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::seeded_rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Spelled numbers that share letters, these are the inputs that catch out
+/// providers which consume a word before looking for the next one.
+const OVERLAPS: [&str; 8] = [
+    "twone",
+    "eightwo",
+    "oneight",
+    "threeight",
+    "fiveight",
+    "nineight",
+    "sevenine",
+    "eighthree",
+];
+
+const FILLER: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+const MAX_TOKENS: usize = 8;
+
+/// Generates `lines` calibration lines.
+///
+/// Every line contains at least one ASCII digit, so the lines are valid for
+/// both parts of day one.
+pub fn generate_calibration(seed: u64, lines: usize) -> String {
+    let mut rng = seeded_rng(seed);
+    return (0..lines)
+        .map(|_| calibration_line(&mut rng))
+        .collect::<Vec<String>>()
+        .join("\n");
+}
+
+fn calibration_line(rng: &mut impl Rng) -> String {
+    let token_count = rng.gen_range(1..=MAX_TOKENS);
+    let digit_position = rng.gen_range(0..token_count);
+    let mut line = String::new();
+
+    for i in 0..token_count {
+        if i == digit_position {
+            line.push(random_digit(rng));
+            continue;
+        }
+
+        match rng.gen_range(0..4) {
+            0 => line.push(random_digit(rng)),
+            1 => line.push_str(WORDS.choose(rng).unwrap()),
+            2 => line.push_str(OVERLAPS.choose(rng).unwrap()),
+            _ => {
+                for _ in 0..rng.gen_range(1..=4) {
+                    line.push(*FILLER.choose(rng).unwrap() as char);
+                }
+            }
+        }
+    }
+
+    return line;
+}
+
+fn random_digit(rng: &mut impl Rng) -> char {
+    return char::from_digit(rng.gen_range(0..=9), 10).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_requested_line_count() {
+        let res = generate_calibration(1, 25);
+        assert_eq!(res.lines().count(), 25);
+    }
+
+    #[test]
+    fn every_line_has_a_digit() {
+        let res = generate_calibration(2, 100);
+        assert!(res.lines().all(|x| x.chars().any(|c| c.is_ascii_digit())));
+    }
+
+    #[test]
+    fn same_seed_same_output() {
+        assert_eq!(generate_calibration(3, 10), generate_calibration(3, 10));
+    }
+
+    #[test]
+    fn digits_include_zero() {
+        let res = generate_calibration(4, 200);
+        assert!(res.contains('0'));
+    }
+}
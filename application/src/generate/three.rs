@@ -0,0 +1,134 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{check_chance, seeded_rng, ConfigError};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchematicConfig {
+    pub width: usize,
+    pub height: usize,
+    /// Chance that a number starts on a free cell.
+    pub number_density: f64,
+    /// Chance that a symbol is placed on a free cell.
+    pub symbol_density: f64,
+    pub symbols: Vec<char>,
+}
+
+impl SchematicConfig {
+    pub fn square(size: usize) -> SchematicConfig {
+        return SchematicConfig {
+            width: size,
+            height: size,
+            ..Default::default()
+        };
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_chance("number_density", self.number_density)?;
+        return check_chance("symbol_density", self.symbol_density);
+    }
+}
+
+impl Default for SchematicConfig {
+    fn default() -> Self {
+        return SchematicConfig {
+            width: 140,
+            height: 140,
+            number_density: 0.1,
+            symbol_density: 0.05,
+            symbols: vec!['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'],
+        };
+    }
+}
+
+const MAX_NUMBER_LENGTH: usize = 3;
+
+/// Generates an engine schematic, numbers on the same line are always
+/// separated by at least one non-digit cell.
+pub fn generate_schematic(seed: u64, config: &SchematicConfig) -> Result<String, ConfigError> {
+    config.validate()?;
+    let mut rng = seeded_rng(seed);
+    return Ok((0..config.height)
+        .map(|_| schematic_line(&mut rng, config))
+        .collect::<Vec<String>>()
+        .join("\n"));
+}
+
+fn schematic_line(rng: &mut impl Rng, config: &SchematicConfig) -> String {
+    let mut line = String::with_capacity(config.width);
+    let mut i = 0;
+
+    while i < config.width {
+        let after_number = line.ends_with(|x: char| x.is_ascii_digit());
+
+        if !after_number && rng.gen_bool(config.number_density) {
+            let length = rng.gen_range(1..=MAX_NUMBER_LENGTH).min(config.width - i);
+            line.push(char::from_digit(rng.gen_range(1..=9), 10).unwrap());
+            for _ in 1..length {
+                line.push(char::from_digit(rng.gen_range(0..=9), 10).unwrap());
+            }
+            i += length;
+            continue;
+        }
+
+        if !config.symbols.is_empty() && rng.gen_bool(config.symbol_density) {
+            line.push(*config.symbols.choose(rng).unwrap());
+        } else {
+            line.push('.');
+        }
+        i += 1;
+    }
+
+    return line;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_requested_size() {
+        let config = SchematicConfig {
+            width: 12,
+            height: 7,
+            ..Default::default()
+        };
+        let res = generate_schematic(1, &config).unwrap();
+        assert_eq!(res.lines().count(), 7);
+        assert!(res.lines().all(|x| x.len() == 12));
+    }
+
+    #[test]
+    fn only_uses_configured_symbols() {
+        let config = SchematicConfig {
+            symbols: vec!['*'],
+            symbol_density: 0.5,
+            ..SchematicConfig::square(20)
+        };
+        let res = generate_schematic(2, &config).unwrap();
+        assert!(res
+            .chars()
+            .all(|x| x.is_ascii_digit() || x == '.' || x == '*' || x == '\n'));
+        assert!(res.contains('*'));
+    }
+
+    #[test]
+    fn rejects_invalid_densities() {
+        let config = SchematicConfig {
+            number_density: 1.5,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_schematic(3, &config),
+            Err(ConfigError::InvalidChance {
+                name: "number_density",
+                value: 1.5
+            })
+        );
+        let config = SchematicConfig {
+            symbol_density: f64::NAN,
+            ..Default::default()
+        };
+        assert!(generate_schematic(3, &config).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{seeded_rng, ConfigError};
+use crate::days::two::parser::colour_index;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+    /// Colours a draw can contain, each one of red, green and blue as day two
+    /// reads them.
+    pub colours: Vec<String>,
+    /// Maximum amount of draws in a single game.
+    pub max_draws: usize,
+    /// Maximum amount of cubes of one colour in a single draw.
+    pub max_cubes: i32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        return GameConfig {
+            colours: vec!["red".to_string(), "green".to_string(), "blue".to_string()],
+            max_draws: 6,
+            max_cubes: 20,
+        };
+    }
+}
+
+impl GameConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.colours.is_empty() {
+            return Err(ConfigError::NotPositive("colours"));
+        }
+        let mut seen = [false; 3];
+        for colour in &self.colours {
+            let Some(index) = colour_index(colour) else {
+                return Err(ConfigError::UnknownColour(colour.clone()));
+            };
+            if seen[index] {
+                return Err(ConfigError::DuplicateColour(colour.clone()));
+            }
+            seen[index] = true;
+        }
+        if self.max_draws < 1 {
+            return Err(ConfigError::NotPositive("max_draws"));
+        }
+        if self.max_cubes < 1 {
+            return Err(ConfigError::NotPositive("max_cubes"));
+        }
+        return Ok(());
+    }
+}
+
+/// Generates `games` game records, numbered from 1.
+pub fn generate_games(seed: u64, games: usize, config: &GameConfig) -> Result<String, ConfigError> {
+    config.validate()?;
+    let mut rng = seeded_rng(seed);
+    return Ok((1..=games)
+        .map(|nr| game_line(&mut rng, nr, config))
+        .collect::<Vec<String>>()
+        .join("\n"));
+}
+
+fn game_line(rng: &mut impl Rng, nr: usize, config: &GameConfig) -> String {
+    let draw_count = rng.gen_range(1..=config.max_draws);
+    let draws: Vec<String> = (0..draw_count).map(|_| draw(rng, config)).collect();
+
+    return format!("Game {}: {}", nr, draws.join("; "));
+}
+
+fn draw(rng: &mut impl Rng, config: &GameConfig) -> String {
+    let colour_count = rng.gen_range(1..=config.colours.len());
+    let colours = config.colours.choose_multiple(rng, colour_count);
+
+    return colours
+        .map(|colour| format!("{} {}", rng.gen_range(1..=config.max_cubes), colour))
+        .collect::<Vec<String>>()
+        .join(", ");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::two::extract_least_cubes;
+
+    #[test]
+    fn generates_requested_game_count() {
+        let res = generate_games(1, 30, &GameConfig::default()).unwrap();
+        assert_eq!(res.lines().count(), 30);
+        assert!(res.lines().last().unwrap().starts_with("Game 30: "));
+    }
+
+    #[test]
+    fn generated_games_parse() {
+        let res = generate_games(2, 50, &GameConfig::default()).unwrap();
+        assert_eq!(extract_least_cubes(&res).unwrap().len(), 50);
+    }
+
+    #[test]
+    fn respects_colours() {
+        let config = GameConfig {
+            colours: vec!["red".to_string()],
+            max_draws: 3,
+            max_cubes: 5,
+        };
+        let res = generate_games(3, 10, &config).unwrap();
+        assert!(!res.contains("green"));
+        assert!(!res.contains("blue"));
+    }
+
+    #[test]
+    fn rejects_empty_choices() {
+        let config = GameConfig {
+            max_draws: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_games(4, 1, &config),
+            Err(ConfigError::NotPositive("max_draws"))
+        );
+        let config = GameConfig {
+            colours: vec![],
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_games(4, 1, &config),
+            Err(ConfigError::NotPositive("colours"))
+        );
+        let config = GameConfig {
+            max_cubes: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_games(4, 1, &config),
+            Err(ConfigError::NotPositive("max_cubes"))
+        );
+    }
+
+    #[test]
+    fn rejects_colours_day_two_cannot_read() {
+        let config = GameConfig {
+            colours: vec!["red".to_string(), "purple".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_games(5, 1, &config),
+            Err(ConfigError::UnknownColour("purple".to_string()))
+        );
+        let config = GameConfig {
+            colours: vec!["red".to_string(), "Reds".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_games(5, 1, &config),
+            Err(ConfigError::DuplicateColour("Reds".to_string()))
+        );
+        let config = GameConfig {
+            colours: vec!["Blues".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            extract_least_cubes(&generate_games(5, 3, &config).unwrap())
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
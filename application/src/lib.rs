@@ -1,4 +1,7 @@
+pub mod answer;
 pub mod days;
+pub mod differential;
+pub mod generate;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
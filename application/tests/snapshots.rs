@@ -0,0 +1,62 @@
+//! Compares the JSON of parsed puzzle inputs against files in `snapshots/`.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the files after an intended
+//! change.
+#![cfg(feature = "serde")]
+
+use std::{env, fs, path::PathBuf};
+
+use application::days::{
+    one::{number_provider::NumberProvider, text_number_provider::TextNumberProvider},
+    three::find_numbers,
+    two::parse_games,
+};
+use serde::Serialize;
+
+const DAY_TWO_EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue";
+
+const DAY_THREE_EXAMPLE: &str = "467..114..
+...*......
+..35..633.";
+
+fn assert_snapshot<T: Serialize>(name: &str, value: &T) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "snapshots", name]
+        .iter()
+        .collect();
+    let actual = serde_json::to_string_pretty(value).unwrap() + "\n";
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}, run with UPDATE_SNAPSHOTS=1", name));
+    assert_eq!(actual, expected, "snapshot {} changed", name);
+}
+
+#[test]
+fn day_one_numbers() {
+    let text = include_str!("../../challenges/one/calibrate_example.txt");
+    assert_snapshot(
+        "day_one_numbers.json",
+        &TextNumberProvider::default().extract_numbers(text),
+    );
+}
+
+#[test]
+fn day_two_games() {
+    assert_snapshot("day_two_games.json", &parse_games(DAY_TWO_EXAMPLE).unwrap());
+}
+
+#[test]
+fn day_two_parse_error() {
+    let res = parse_games("Game 1: 3 blue\nGame 2: 3 purple").unwrap_err();
+    assert_snapshot("day_two_parse_error.json", &res);
+}
+
+#[test]
+fn day_three_numbers() {
+    assert_snapshot("day_three_numbers.json", &find_numbers(DAY_THREE_EXAMPLE).unwrap());
+}
@@ -0,0 +1,39 @@
+use application::differential::{self, one, three, two};
+use clap::Args;
+
+use crate::generate::GenerateDay;
+
+#[derive(Args, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct DifferentialArgs {
+    day: GenerateDay,
+
+    /// Seed of the first run, every following run uses the next seed
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    #[clap(long, default_value_t = 100)]
+    size: usize,
+
+    #[clap(long, default_value_t = 100)]
+    runs: usize,
+}
+
+pub fn command_differential(args: DifferentialArgs) {
+    let check = match args.day {
+        GenerateDay::One => one::check,
+        GenerateDay::Two => two::check,
+        GenerateDay::Three => three::check,
+    };
+
+    match differential::run(check, args.seed, args.size, args.runs) {
+        Ok(runs) => println!("All {} runs match the reference", runs),
+        Err(mismatch) => {
+            println!("Mismatch for seed {}", mismatch.seed);
+            println!("Expected: {}", mismatch.expected);
+            println!("Actual:   {}", mismatch.actual);
+            println!("Input:\n{}", mismatch.input);
+            std::process::exit(1);
+        }
+    }
+}
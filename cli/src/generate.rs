@@ -0,0 +1,38 @@
+use application::generate::{
+    one::generate_calibration,
+    three::{generate_schematic, SchematicConfig},
+    two::{generate_games, GameConfig},
+};
+use clap::{Args, ValueEnum};
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum GenerateDay {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Args, Debug)]
+#[command(author, version, about, long_about = None)]
+pub struct GenerateArgs {
+    day: GenerateDay,
+
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Amount of lines for day one and two, width and height for day three
+    #[clap(long, default_value_t = 100)]
+    size: usize,
+}
+
+pub fn command_generate(args: GenerateArgs) {
+    let result = match args.day {
+        GenerateDay::One => generate_calibration(args.seed, args.size),
+        GenerateDay::Two => generate_games(args.seed, args.size, &GameConfig::default())
+            .unwrap_or_else(|x| panic!("Unable to generate games: {}", x)),
+        GenerateDay::Three => generate_schematic(args.seed, &SchematicConfig::square(args.size))
+            .unwrap_or_else(|x| panic!("Unable to generate schematic: {}", x)),
+    };
+
+    println!("{}", result);
+}
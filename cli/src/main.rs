@@ -1,9 +1,13 @@
+use differential::command_differential;
+use generate::command_generate;
 use one::command_one;
 use three::command_three;
 use two::command_two;
 
 use clap::{Parser, Subcommand};
 
+mod differential;
+mod generate;
 mod one;
 mod two;
 mod three;
@@ -19,6 +23,8 @@ struct AocCli {
 enum MainCommands {
     #[command(subcommand)]
     Day(Day),
+    Generate(generate::GenerateArgs),
+    Differential(differential::DifferentialArgs),
 }
 
 #[derive(Subcommand, Debug)]
@@ -44,5 +50,11 @@ fn main() {
                 command_three(args);
             }
         },
+        MainCommands::Generate(args) => {
+            command_generate(args);
+        }
+        MainCommands::Differential(args) => {
+            command_differential(args);
+        }
     }
 }
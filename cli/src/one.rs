@@ -1,13 +1,35 @@
 use std::fs;
 
 use application::{
-    self,
+    answer::Answer,
     days::one::{
-        default_number_provider::DefaultNumberProvider, number_provider::NumberProvider,
+        default_number_provider::DefaultNumberProvider,
+        fuzzy_number_provider::FuzzyNumberProvider,
+        number_provider::NumberProvider,
+        pattern_number_provider::PatternNumberProvider,
+        strategy::{calibrate_with, CalibrationStrategy, Combine},
         text_number_provider::TextNumberProvider,
+        unicode_number_provider::UnicodeNumberProvider,
     },
 };
-use clap::Args;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use util::trie::{MatchOptions, Normalisation};
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum StrategyArg {
+    FirstLast,
+    FirstLastK,
+    Full,
+    Sum,
+    Product,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum NormalisationArg {
+    Nfc,
+    Nfkc,
+}
 
 #[derive(Args, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -20,18 +42,137 @@ pub struct DayOneCalibrateArgs {
 
     #[clap(long, default_value_t = 1)]
     part: i32,
+
+    /// Recognise decimal digits of every script, such as ３ or ٣, only for part 1
+    #[clap(long, default_value_t = false)]
+    unicode: bool,
+
+    /// Match spelled numbers regardless of case, only for part 2
+    #[clap(long, default_value_t = false)]
+    ignore_case: bool,
+
+    /// Unicode normalisation applied before matching spelled numbers, only for part 2
+    #[clap(long, value_enum)]
+    normalise: Option<NormalisationArg>,
+
+    /// Characters skipped inside spelled numbers, such as '-_', only for part 2
+    #[clap(long, default_value = "")]
+    ignore: String,
+
+    /// Also read spelled numbers with up to this many typos, only for part 2
+    #[clap(long)]
+    fuzzy: Option<usize>,
+
+    /// File with one 'pattern = value' rule per line, replaces the numbers of --part
+    #[clap(long)]
+    patterns: Option<String>,
+
+    /// How the numbers of a line are combined
+    #[clap(long, value_enum, default_value_t = StrategyArg::FirstLast)]
+    strategy: StrategyArg,
+
+    /// Amount of numbers taken from both ends by the first-last-k strategy
+    #[clap(short = 'k', long = "take", default_value_t = 1)]
+    k: usize,
+
+    /// Base the digits are read in
+    #[clap(long, default_value_t = 10)]
+    base: u32,
+
+    /// Print the numbers of every line and the calibration values as JSON
+    #[clap(long, default_value_t = false)]
+    json: bool,
 }
 
 pub fn command_one(args: DayOneCalibrateArgs) {
     let contents = fs::read_to_string(&args.text_path).expect("Unable to read text_path file");
 
-    let number_provider: &dyn NumberProvider = match args.part {
-        1 => &DefaultNumberProvider {},
-        2 => &TextNumberProvider {},
-        _ => panic!("Unexpected part, choose 1 or 2"),
+    let combine = match args.strategy {
+        StrategyArg::FirstLast => Combine::FirstLast,
+        StrategyArg::FirstLastK => Combine::FirstLastK(args.k),
+        StrategyArg::Full => Combine::Full,
+        StrategyArg::Sum => Combine::Sum,
+        StrategyArg::Product => Combine::Product,
     };
+    let strategy = CalibrationStrategy::new(combine, args.base)
+        .unwrap_or_else(|x| panic!("Unable to use strategy: {}", x));
 
-    let result = application::days::one::calibrate(&contents, number_provider);
+    match args.part {
+        _ if args.patterns.is_some() => {
+            let rules = fs::read_to_string(args.patterns.as_ref().unwrap())
+                .expect("Unable to read patterns file");
+            let number_provider = PatternNumberProvider::parse(&rules)
+                .unwrap_or_else(|x| panic!("Unable to parse patterns file: {}", x));
+            calibrate_and_print(&args, &contents, &number_provider, &strategy);
+        }
+        1 if args.unicode => {
+            calibrate_and_print(&args, &contents, &UnicodeNumberProvider {}, &strategy)
+        }
+        1 => calibrate_and_print(&args, &contents, &DefaultNumberProvider {}, &strategy),
+        2 if args.unicode => panic!("Unicode digits are only supported for part 1"),
+        2 if args.fuzzy.is_some() => {
+            let number_provider = FuzzyNumberProvider {
+                max_distance: args.fuzzy.unwrap(),
+            };
+            if args.full_output && !args.json {
+                print_corrections(&contents, &number_provider);
+            }
+            calibrate_and_print(&args, &contents, &number_provider, &strategy);
+        }
+        2 => {
+            let number_provider = TextNumberProvider::new(MatchOptions {
+                case_insensitive: args.ignore_case,
+                normalisation: args.normalise.map(|x| match x {
+                    NormalisationArg::Nfc => Normalisation::Nfc,
+                    NormalisationArg::Nfkc => Normalisation::Nfkc,
+                }),
+                ignored: args.ignore.chars().collect(),
+            });
+            calibrate_and_print(&args, &contents, &number_provider, &strategy);
+        }
+        _ => panic!("Unexpected part, choose 1 or 2"),
+    }
+}
+
+fn print_corrections(contents: &str, number_provider: &FuzzyNumberProvider) {
+    for (i, line) in contents.split('\n').enumerate() {
+        for correction in number_provider.corrections(line) {
+            println!(
+                "Line {}: read '{}' as {}, edit distance {}",
+                i + 1,
+                correction.text,
+                correction.value,
+                correction.distance
+            );
+        }
+    }
+}
+
+/// Serialised directly instead of through `serde_json::Value`, which cannot
+/// hold values above `u64::MAX`.
+#[derive(Serialize)]
+struct CalibrationDump {
+    numbers: Vec<Vec<u32>>,
+    values: Vec<Answer>,
+}
+
+fn calibrate_and_print<P: NumberProvider>(
+    args: &DayOneCalibrateArgs,
+    contents: &str,
+    number_provider: &P,
+    strategy: &CalibrationStrategy,
+) {
+    let result = calibrate_with(contents, number_provider, strategy)
+        .unwrap_or_else(|x| panic!("Unable to calibrate: {}", x));
+
+    if args.json {
+        let dump = CalibrationDump {
+            numbers: number_provider.extract_numbers(contents),
+            values: result,
+        };
+        println!("{}", serde_json::to_string_pretty(&dump).unwrap());
+        return;
+    }
 
     if args.full_output {
         for res in &result {
@@ -39,8 +180,7 @@ pub fn command_one(args: DayOneCalibrateArgs) {
         }
     }
 
-    println!(
-        "The result for your input is: {}",
-        result.iter().sum::<u32>()
-    );
+    let answer = Answer::sum(result).unwrap_or_else(|x| panic!("Unable to compute answer: {}", x));
+
+    println!("The result for your input is: {}", answer);
 }
@@ -1,5 +1,7 @@
 use std::fs;
 
+use application::answer::Answer;
+use application::days::three::rules::{Adjacency, AdjacencyRules, Symbols};
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -7,15 +9,60 @@ use clap::Args;
 pub struct DayThreePartsArgs {
     #[arg(short = 'p')]
     text_path: String,
+
+    /// Ignore diagonal neighbours
+    #[clap(long, default_value_t = false)]
+    orthogonal: bool,
+
+    #[clap(long, default_value_t = 1)]
+    distance: usize,
+
+    /// Characters that are neither digits nor symbols
+    #[clap(long, default_value = ".")]
+    empty: String,
+
+    /// Only treat these characters as symbols, instead of everything that is not empty
+    #[clap(long)]
+    symbols: Option<String>,
+
+    /// Treat the schematic as a torus
+    #[clap(long, default_value_t = false)]
+    wrap: bool,
+
+    /// Print every number found on every line as JSON
+    #[clap(long, default_value_t = false)]
+    json: bool,
 }
 
 pub fn command_three(args: DayThreePartsArgs) {
     let contents = fs::read_to_string(&args.text_path).expect("Unable to read text_path file");
 
-    let result = application::days::three::find_valid_numbers(&contents);
+    if args.json {
+        let numbers = application::days::three::find_numbers(&contents)
+            .unwrap_or_else(|x| panic!("Unable to read numbers: {}", x));
+        println!("{}", serde_json::to_string_pretty(&numbers).unwrap());
+        return;
+    }
+
+    let rules = AdjacencyRules {
+        adjacency: if args.orthogonal {
+            Adjacency::Orthogonal
+        } else {
+            Adjacency::Diagonal
+        },
+        distance: args.distance,
+        empty: args.empty.chars().collect(),
+        symbols: match args.symbols {
+            Some(symbols) => Symbols::Only(symbols.chars().collect()),
+            None => Symbols::AnyNonEmpty,
+        },
+        wrap: args.wrap,
+    };
+
+    let result = application::days::three::find_valid_numbers(&contents, &rules)
+        .unwrap_or_else(|x| panic!("Unable to read numbers: {}", x));
+
+    let answer = Answer::sum(result).unwrap_or_else(|x| panic!("Unable to compute answer: {}", x));
 
-    println!(
-        "The result for your input is: {}",
-        result.into_iter().sum::<u32>()
-    );
+    println!("The result for your input is: {}", answer);
 }
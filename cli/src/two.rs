@@ -1,5 +1,6 @@
 use std::fs;
 
+use application::answer::Answer;
 use clap::Args;
 
 #[derive(Args, Debug)]
@@ -10,24 +11,37 @@ pub struct DayTwoCalibrateArgs {
 
     #[clap(long, default_value_t = 1)]
     part: i32,
+
+    /// Print the parsed games as JSON
+    #[clap(long, default_value_t = false)]
+    json: bool,
 }
 
 pub fn command_two(args: DayTwoCalibrateArgs) {
     let contents = fs::read_to_string(&args.text_path).expect("Unable to read text_path file");
 
+    if args.json {
+        let games = application::days::two::parse_games(&contents)
+            .unwrap_or_else(|x| panic!("Unable to parse games: {}", x));
+        println!("{}", serde_json::to_string_pretty(&games).unwrap());
+        return;
+    }
+
     if args.part == 1 {
-        let result = application::days::two::extract_possible_games(&contents, 12, 14, 13);
+        let result = application::days::two::extract_possible_games(&contents, 12, 14, 13)
+            .unwrap_or_else(|x| panic!("Unable to parse games: {}", x));
 
-        println!(
-            "The result for your input is: {}",
-            result.into_iter().map(|x| x.nr).sum::<i32>()
-        );
+        let answer = Answer::sum(result.into_iter().map(|x| x.nr))
+            .unwrap_or_else(|x| panic!("Unable to compute answer: {}", x));
+
+        println!("The result for your input is: {}", answer);
     } else {
-        let result = application::days::two::extract_least_cubes(&contents);
+        let result = application::days::two::extract_least_cubes(&contents)
+            .unwrap_or_else(|x| panic!("Unable to parse games: {}", x));
+
+        let answer = Answer::sum(result.into_iter().map(|x| x.power()))
+            .unwrap_or_else(|x| panic!("Unable to compute answer: {}", x));
 
-        println!(
-            "The result for your input is: {}",
-            result.into_iter().map(|x| x.power()).sum::<i32>()
-        );
+        println!("The result for your input is: {}", answer);
     }
 }
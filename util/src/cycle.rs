@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A sequence of states that repeats from step `start` on, every `length`
+/// steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        return self.start + (n - self.start) % self.length;
+    }
+
+    /// Value at step `n` of a quantity that grows by the same amount every
+    /// cycle, like a score, from its values at the first steps.
+    ///
+    /// Panics if `values` does not reach step `start + length`.
+    pub fn extrapolate(&self, values: &[i64], n: usize) -> i64 {
+        if n < values.len() {
+            return values[n];
+        }
+        let growth = values[self.start + self.length] - values[self.start];
+        let cycles = ((n - self.start) / self.length) as i64;
+        return values[self.reduce(n)] + growth * cycles;
+    }
+}
+
+/// Floyd's tortoise and hare, keeping two states at a time. Loops forever if
+/// the states never repeat.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The distance between them is now a multiple of the length, so they
+    // meet again at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    return Cycle { start, length };
+}
+
+/// Brent's algorithm, keeping two states at a time like `floyd` but taking
+/// fewer steps. Loops forever if the states never repeat.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    return Cycle { start, length };
+}
+
+/// Every state up to and including the first repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History<S> {
+    /// `states[start + length]` is the first state seen twice.
+    pub states: Vec<S>,
+    pub cycle: Cycle,
+}
+
+impl<S> History<S> {
+    pub fn state_at(&self, n: usize) -> &S {
+        return &self.states[self.cycle.reduce(n)];
+    }
+}
+
+/// Remembers every state until one repeats. Loops forever if the states
+/// never repeat.
+pub fn find_repeat<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> History<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let current = states.last().unwrap();
+        if let Some(&start) = seen.get(current) {
+            let length = states.len() - 1 - start;
+            return History {
+                states,
+                cycle: Cycle { start, length },
+            };
+        }
+        seen.insert(current.clone(), states.len() - 1);
+        let next = step(current);
+        states.push(next);
+    }
+}
+
+/// The state after `n` steps, skipping whole cycles once a state repeats.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![initial];
+    while states.len() <= n {
+        let current = states.last().unwrap();
+        if let Some(&start) = seen.get(current) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - 1 - start,
+            };
+            return states.swap_remove(cycle.reduce(n));
+        }
+        seen.insert(current.clone(), states.len() - 1);
+        let next = step(current);
+        states.push(next);
+    }
+    return states.swap_remove(n);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, ..., 4 and then 5, 6, 7, 8 over and over.
+    fn tail_and_loop(x: &u32) -> u32 {
+        return if *x == 8 { 5 } else { x + 1 };
+    }
+
+    /// Quadratic generator on small numbers, with tails of varying length.
+    fn scramble(x: &u64) -> u64 {
+        return (x * x + 1) % 255;
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let expected = Cycle {
+            start: 5,
+            length: 4,
+        };
+        assert_eq!(floyd(0, tail_and_loop), expected);
+        assert_eq!(brent(0, tail_and_loop), expected);
+        assert_eq!(find_repeat(0, tail_and_loop).cycle, expected);
+
+        for initial in 0..255 {
+            let res = find_repeat(initial, scramble).cycle;
+            assert_eq!(floyd(initial, scramble), res, "from {}", initial);
+            assert_eq!(brent(initial, scramble), res, "from {}", initial);
+        }
+    }
+
+    #[test]
+    fn cycle_from_the_start() {
+        let expected = Cycle {
+            start: 0,
+            length: 3,
+        };
+        let step = |x: &u8| (x + 1) % 3;
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_repeat(0, step).cycle, expected);
+        assert_eq!(
+            floyd(7, |x| *x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn history_states() {
+        let res = find_repeat(0, tail_and_loop);
+        assert_eq!(res.states, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 5]);
+        assert_eq!(*res.state_at(3), 3);
+        assert_eq!(*res.state_at(9), 5);
+        assert_eq!(*res.state_at(1_000_000_000), 5 + (1_000_000_000 - 5) % 4);
+    }
+
+    #[test]
+    fn state_after_many_steps() {
+        assert_eq!(state_at(0, tail_and_loop, 2), 2);
+        assert_eq!(state_at(0, tail_and_loop, 10), 6);
+        assert_eq!(state_at(0, tail_and_loop, 1_000_000_000_000), 8);
+
+        let mut x = 3;
+        for _ in 0..1000 {
+            x = scramble(&x);
+        }
+        assert_eq!(state_at(3, scramble, 1000), x);
+    }
+
+    #[test]
+    fn extrapolate_growing_value() {
+        // The score gains the state after each step.
+        let history = find_repeat(0, tail_and_loop);
+        let mut scores = vec![0i64];
+        for state in &history.states[1..] {
+            scores.push(scores.last().unwrap() + *state as i64);
+        }
+
+        let mut state = 0;
+        let mut score = 0;
+        for n in 1..=100 {
+            state = tail_and_loop(&state);
+            score += state as i64;
+            assert_eq!(history.cycle.extrapolate(&scores, n), score, "step {}", n);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::geom::{Direction, Point};
+
+/// Disjoint-set union over the elements `0..len`, with path compression and
+/// union by rank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dsu {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl Dsu {
+    /// Every element starts in a set of its own.
+    pub fn new(len: usize) -> Dsu {
+        return Dsu {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            set_count: len,
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.parents.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.parents.is_empty();
+    }
+
+    /// Number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        return self.set_count;
+    }
+
+    /// The representative of the set holding `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = x;
+        while current != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        return root;
+    }
+
+    /// Joins the sets of `a` and `b`, false if they already were one set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (parent, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+        self.set_count -= 1;
+        return true;
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        return self.find(a) == self.find(b);
+    }
+
+    /// Size of the set holding `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        return self.sizes[root];
+    }
+
+    /// Every set with its elements in increasing order, sets ordered by their
+    /// smallest element.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut indices: Vec<Option<usize>> = vec![None; self.len()];
+        let mut res: Vec<Vec<usize>> = vec![];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let index = *indices[root].get_or_insert_with(|| {
+                res.push(vec![]);
+                res.len() - 1
+            });
+            res[index].push(x);
+        }
+        return res;
+    }
+}
+
+/// Groups the cells passing `include` that are joined by steps in
+/// `directions`. Components and their cells come in reading order.
+///
+/// Takes rows rather than a `Grid` because rows may differ in length, like
+/// the lines of a text, see `Grid::components` for rectangular grids.
+pub fn grid_components<T, R: AsRef<[T]>>(
+    grid: &[R],
+    include: impl Fn(&T) -> bool,
+    directions: &[Direction],
+) -> Vec<Vec<Point>> {
+    let mut cells: Vec<Point> = vec![];
+    let mut row_starts: Vec<usize> = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        row_starts.push(cells.len());
+        cells.extend(
+            row.as_ref()
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| include(x))
+                .map(|(x, _)| Point::new(x as i64, y as i64)),
+        );
+    }
+    let index_of = |point: Point| -> Option<usize> {
+        let y = usize::try_from(point.y).ok()?;
+        let start = *row_starts.get(y)?;
+        let end = row_starts.get(y + 1).copied().unwrap_or(cells.len());
+        let offset = cells[start..end]
+            .binary_search_by_key(&point.x, |x| x.x)
+            .ok()?;
+        return Some(start + offset);
+    };
+
+    let mut dsu = Dsu::new(cells.len());
+    for (i, cell) in cells.iter().enumerate() {
+        for direction in directions {
+            if let Some(other) = index_of(cell.step(*direction)) {
+                dsu.union(i, other);
+            }
+        }
+    }
+
+    return dsu
+        .sets()
+        .into_iter()
+        .map(|x| x.into_iter().map(|i| cells[i]).collect())
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Vec<Vec<char>> {
+        return text.lines().map(|x| x.chars().collect()).collect();
+    }
+
+    #[test]
+    fn union_and_find() {
+        let mut sut = Dsu::new(6);
+        assert_eq!(sut.set_count(), 6);
+        assert!(sut.union(0, 1));
+        assert!(sut.union(2, 3));
+        assert!(sut.union(1, 3));
+        assert!(!sut.union(0, 2));
+        assert!(sut.same(0, 3));
+        assert!(!sut.same(0, 4));
+        assert_eq!(sut.size(2), 4);
+        assert_eq!(sut.size(5), 1);
+        assert_eq!(sut.set_count(), 3);
+        assert_eq!(sut.sets(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn long_chain_compresses() {
+        let mut sut = Dsu::new(10_000);
+        for i in 1..sut.len() {
+            sut.union(i - 1, i);
+        }
+        assert_eq!(sut.set_count(), 1);
+        assert_eq!(sut.size(0), 10_000);
+        assert!(sut.same(0, 9_999));
+    }
+
+    #[test]
+    fn empty_dsu() {
+        let mut sut = Dsu::new(0);
+        assert!(sut.is_empty());
+        assert_eq!(sut.sets(), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn orthogonal_components() {
+        let map = grid("##.#\n#..#\n..##\n#");
+        let res = grid_components(&map, |x| *x == '#', &Direction::ORTHOGONAL);
+        assert_eq!(
+            res,
+            vec![
+                vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)],
+                vec![
+                    Point::new(3, 0),
+                    Point::new(3, 1),
+                    Point::new(2, 2),
+                    Point::new(3, 2)
+                ],
+                vec![Point::new(0, 3)],
+            ]
+        );
+    }
+
+    #[test]
+    fn diagonal_components() {
+        let map = grid("#.#\n.#.\n...\n..#");
+        let res = grid_components(&map, |x| *x == '#', &Direction::ALL);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].len(), 3);
+        assert_eq!(res[1], vec![Point::new(2, 3)]);
+    }
+
+    #[test]
+    fn horizontal_components() {
+        let map = grid("11.2\n1..2");
+        let res = grid_components(
+            &map,
+            |x| x.is_ascii_digit(),
+            &[Direction::East, Direction::West],
+        );
+        assert_eq!(res.len(), 4);
+        assert_eq!(res[0], vec![Point::new(0, 0), Point::new(1, 0)]);
+    }
+}
@@ -0,0 +1,365 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::math::gcd;
+
+/// A cell of a grid, `y` grows downwards like the rows of a text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        return Point { x, y };
+    }
+
+    pub fn manhattan(&self, other: Point) -> i64 {
+        return (other - *self).manhattan_length();
+    }
+
+    /// The point one step away in `direction`.
+    pub fn step(&self, direction: Direction) -> Point {
+        return *self + direction.vector();
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Vector {
+        return Vector { x, y };
+    }
+
+    /// Number of horizontal and vertical steps needed to cover the vector.
+    pub fn manhattan_length(&self) -> i64 {
+        return self.x.abs() + self.y.abs();
+    }
+
+    /// Number of steps needed when diagonal steps are allowed too.
+    pub fn chebyshev_length(&self) -> i64 {
+        return self.x.abs().max(self.y.abs());
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        return Point::new(self.x + rhs.x, self.y + rhs.y);
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        return Point::new(self.x - rhs.x, self.y - rhs.y);
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        return Vector::new(self.x - rhs.x, self.y - rhs.y);
+    }
+}
+
+impl Add<Vector> for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        return Vector::new(self.x + rhs.x, self.y + rhs.y);
+    }
+}
+
+impl Sub<Vector> for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        return Vector::new(self.x - rhs.x, self.y - rhs.y);
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        return Vector::new(self.x * rhs, self.y * rhs);
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        return Vector::new(-self.x, -self.y);
+    }
+}
+
+/// The eight compass directions, north is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4-way directions, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The 8-way directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn vector(self) -> Vector {
+        return match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::NorthEast => Vector::new(1, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::SouthEast => Vector::new(1, 1),
+            Direction::South => Vector::new(0, 1),
+            Direction::SouthWest => Vector::new(-1, 1),
+            Direction::West => Vector::new(-1, 0),
+            Direction::NorthWest => Vector::new(-1, -1),
+        };
+    }
+
+    /// Turns clockwise by `eighths` of a full turn, negative turns
+    /// counterclockwise.
+    pub fn rotate(self, eighths: i32) -> Direction {
+        let index = (self as i32 + eighths).rem_euclid(8);
+        return Direction::ALL[index as usize];
+    }
+
+    pub fn turn_right(self) -> Direction {
+        return self.rotate(2);
+    }
+
+    pub fn turn_left(self) -> Direction {
+        return self.rotate(-2);
+    }
+
+    pub fn opposite(self) -> Direction {
+        return self.rotate(4);
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        return self as i32 % 2 == 1;
+    }
+}
+
+/// The smallest rectangle holding a set of points, both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// `None` when there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<BoundingBox> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut res = BoundingBox {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            res.include(point);
+        }
+        return Some(res);
+    }
+
+    /// Grows the box to hold `point`.
+    pub fn include(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return self.min.x <= point.x
+            && point.x <= self.max.x
+            && self.min.y <= point.y
+            && point.y <= self.max.y;
+    }
+
+    pub fn width(&self) -> i64 {
+        return self.max.x - self.min.x + 1;
+    }
+
+    pub fn height(&self) -> i64 {
+        return self.max.y - self.min.y + 1;
+    }
+
+    /// Every point of the box, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (min, max) = (self.min, self.max);
+        return (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)));
+    }
+
+    /// Moves `point` into the box as if the opposite edges were glued
+    /// together.
+    pub fn wrap(&self, point: Point) -> Point {
+        return Point::new(
+            self.min.x + (point.x - self.min.x).rem_euclid(self.width()),
+            self.min.y + (point.y - self.min.y).rem_euclid(self.height()),
+        );
+    }
+}
+
+/// Twice the area of the polygon with `vertices` in order, by the shoelace
+/// formula. Doubling keeps the result exact for points on a grid.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    let mut res = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        res += a.x * b.y - b.x * a.y;
+    }
+    return res.abs();
+}
+
+/// Number of grid points on the edges of the polygon.
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    let mut res = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let edge = vertices[(i + 1) % vertices.len()] - *a;
+        res += gcd(edge.x, edge.y);
+    }
+    return res;
+}
+
+/// Number of grid points strictly inside the polygon, by Pick's theorem.
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    return (double_area(vertices) - boundary_points(vertices) + 2) / 2;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(2, 3);
+        let b = Point::new(-1, 7);
+        assert_eq!(b - a, Vector::new(-3, 4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!((b - a).chebyshev_length(), 4);
+        assert_eq!(-(b - a) * 2, Vector::new(6, -8));
+        let mut c = a;
+        c += Vector::new(1, 1);
+        c -= Vector::new(0, 2);
+        assert_eq!(c, Point::new(3, 2));
+    }
+
+    #[test]
+    fn directions_turn() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthWest.opposite(), Direction::NorthEast);
+        assert_eq!(Direction::NorthWest.rotate(1), Direction::North);
+        assert_eq!(Direction::North.rotate(-9), Direction::NorthWest);
+        assert!(Direction::SouthEast.is_diagonal());
+        assert!(!Direction::West.is_diagonal());
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.vector() + direction.opposite().vector(),
+                Vector::ZERO
+            );
+            assert_eq!(direction.vector().chebyshev_length(), 1);
+        }
+        for direction in Direction::ORTHOGONAL {
+            assert_eq!(direction.vector().manhattan_length(), 1);
+        }
+        assert_eq!(Point::ORIGIN.step(Direction::North), Point::new(0, -1));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = [Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)];
+        let sut = BoundingBox::from_points(points).unwrap();
+        assert_eq!(sut.min, Point::new(-2, -1));
+        assert_eq!(sut.max, Point::new(3, 4));
+        assert_eq!((sut.width(), sut.height()), (6, 6));
+        assert!(sut.contains(Point::new(3, 4)));
+        assert!(!sut.contains(Point::new(4, 4)));
+        assert_eq!(sut.points().count(), 36);
+        assert_eq!(sut.points().next(), Some(sut.min));
+        assert_eq!(BoundingBox::from_points([]), None);
+    }
+
+    #[test]
+    fn wrap_into_box() {
+        let sut = BoundingBox {
+            min: Point::ORIGIN,
+            max: Point::new(4, 2),
+        };
+        assert_eq!(sut.wrap(Point::new(-1, -1)), Point::new(4, 2));
+        assert_eq!(sut.wrap(Point::new(5, 3)), Point::ORIGIN);
+        assert_eq!(sut.wrap(Point::new(2, 1)), Point::new(2, 1));
+    }
+
+    #[test]
+    fn polygon_area_and_points() {
+        let square = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(0, 4),
+        ];
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        let triangle = [Point::new(0, 0), Point::new(0, 3), Point::new(3, 0)];
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+
+        // Counterclockwise order gives the same area.
+        let reversed: Vec<Point> = square.iter().rev().copied().collect();
+        assert_eq!(double_area(&reversed), 32);
+    }
+}
@@ -0,0 +1,500 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::dsu::grid_components;
+use crate::geom::{BoundingBox, Direction, Point};
+
+/// A rectangular grid stored row by row, `x` is the column and `y` the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// A row whose length differs from the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaggedRow {
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for RaggedRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row, self.found, self.expected
+        );
+    }
+}
+
+impl std::error::Error for RaggedRow {}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Grid<T> {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(cell(Point::new(x as i64, y as i64)));
+            }
+        }
+        return Grid {
+            width,
+            height,
+            cells,
+        };
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, RaggedRow> {
+        let width = rows.first().map(|x| x.len()).unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (row, line) in rows.into_iter().enumerate() {
+            if line.len() != width {
+                return Err(RaggedRow {
+                    row,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+            cells.extend(line);
+        }
+        return Ok(Grid {
+            width,
+            height,
+            cells,
+        });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    /// Box around every cell, `None` when the grid has no cells.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        return Some(BoundingBox {
+            min: Point::ORIGIN,
+            max: Point::new(self.width as i64 - 1, self.height as i64 - 1),
+        });
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        return (0..self.width as i64).contains(&point.x)
+            && (0..self.height as i64).contains(&point.y);
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        let index = self.index_of(point)?;
+        return Some(&self.cells[index]);
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        let index = self.index_of(point)?;
+        return Some(&mut self.cells[index]);
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return (0..self.height).map(|y| self.row(y));
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        return self.cells.iter().skip(x).step_by(self.width);
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        return self.cells.iter().enumerate().map(move |(i, x)| {
+            let point = Point::new((i % width) as i64, (i / width) as i64);
+            (point, x)
+        });
+    }
+
+    /// Groups the cells passing `include` that are joined by steps in
+    /// `directions`, see `dsu::grid_components`.
+    pub fn components(
+        &self,
+        include: impl Fn(&T) -> bool,
+        directions: &[Direction],
+    ) -> Vec<Vec<Point>> {
+        let rows: Vec<&[T]> = self.rows().collect();
+        return grid_components(&rows, include, directions);
+    }
+
+    /// Panics unless `row` is as wide as the grid, an empty grid takes the
+    /// width of its first row.
+    pub fn insert_row(&mut self, y: usize, row: Vec<T>) {
+        assert!(y <= self.height, "Row {} is outside the grid", y);
+        if self.height == 0 {
+            self.width = row.len();
+        }
+        assert_eq!(row.len(), self.width, "Row does not fit the grid");
+        let at = y * self.width;
+        self.cells.splice(at..at, row);
+        self.height += 1;
+    }
+
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.height, "Row {} is outside the grid", y);
+        let res = self
+            .cells
+            .drain(y * self.width..(y + 1) * self.width)
+            .collect();
+        self.height -= 1;
+        return res;
+    }
+
+    /// Panics unless `column` is as high as the grid.
+    pub fn insert_column(&mut self, x: usize, column: Vec<T>) {
+        assert!(x <= self.width, "Column {} is outside the grid", x);
+        assert_eq!(column.len(), self.height, "Column does not fit the grid");
+        let mut old = std::mem::take(&mut self.cells).into_iter();
+        let mut column = column.into_iter();
+        for _ in 0..self.height {
+            self.cells.extend(old.by_ref().take(x));
+            self.cells.extend(column.next());
+            self.cells.extend(old.by_ref().take(self.width - x));
+        }
+        self.width += 1;
+    }
+
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        let mut res = Vec::with_capacity(self.height);
+        let mut kept = Vec::with_capacity(self.cells.len() - self.height);
+        for (i, cell) in std::mem::take(&mut self.cells).into_iter().enumerate() {
+            if i % self.width == x {
+                res.push(cell);
+            } else {
+                kept.push(cell);
+            }
+        }
+        self.cells = kept;
+        self.width -= 1;
+        return res;
+    }
+
+    /// Slides every cell passing `rolls` towards `direction` until it meets
+    /// the edge or a cell that is not `is_empty`.
+    ///
+    /// Panics for diagonal directions.
+    pub fn tilt(
+        &mut self,
+        direction: Direction,
+        rolls: impl Fn(&T) -> bool,
+        is_empty: impl Fn(&T) -> bool,
+    ) {
+        assert!(
+            !direction.is_diagonal(),
+            "Cannot tilt towards {:?}",
+            direction
+        );
+        let Some(bounds) = self.bounds() else {
+            return;
+        };
+        let back = -direction.vector();
+        // The cells on the edge the cells slide towards.
+        let edge: Vec<Point> = bounds
+            .points()
+            .filter(|x| !bounds.contains(*x + direction.vector()))
+            .collect();
+
+        for start in edge {
+            let mut free = start;
+            let mut point = start;
+            while self.contains(point) {
+                let index = self.index_of(point).unwrap();
+                if rolls(&self.cells[index]) {
+                    let free_index = self.index_of(free).unwrap();
+                    self.cells.swap(index, free_index);
+                    free += back;
+                } else if !is_empty(&self.cells[index]) {
+                    free = point + back;
+                }
+                point += back;
+            }
+        }
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        if !self.contains(point) {
+            return None;
+        }
+        return Some(point.y as usize * self.width + point.x as usize);
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        return Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        };
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T> {
+        return Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        });
+    }
+
+    /// Turns a quarter clockwise.
+    pub fn rotate_right(&self) -> Grid<T> {
+        let last_row = self.height as i64 - 1;
+        return Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, last_row - p.x)].clone()
+        });
+    }
+
+    /// Turns a quarter counterclockwise.
+    pub fn rotate_left(&self) -> Grid<T> {
+        let last_column = self.width as i64 - 1;
+        return Grid::from_fn(self.height, self.width, |p| {
+            self[Point::new(last_column - p.y, p.x)].clone()
+        });
+    }
+
+    /// Mirrors left and right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let last_column = self.width as i64 - 1;
+        return Grid::from_fn(self.width, self.height, |p| {
+            self[Point::new(last_column - p.x, p.y)].clone()
+        });
+    }
+
+    /// Mirrors top and bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let last_row = self.height as i64 - 1;
+        return Grid::from_fn(self.width, self.height, |p| {
+            self[Point::new(p.x, last_row - p.y)].clone()
+        });
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Every `y` for which the rows above it mirror the rows from `y` on, up
+    /// to whichever edge comes first, with exactly `differences` cells that do
+    /// not match their mirror image.
+    pub fn reflection_rows(&self, differences: usize) -> Vec<usize> {
+        return (1..self.height)
+            .filter(|&y| {
+                let pairs =
+                    (0..y.min(self.height - y)).map(|i| (self.row(y - 1 - i), self.row(y + i)));
+                let mut count = 0;
+                for (a, b) in pairs {
+                    count += a.iter().zip(b).filter(|(a, b)| a != b).count();
+                }
+                count == differences
+            })
+            .collect();
+    }
+
+    /// Like `reflection_rows`, for a mirror between columns `x - 1` and `x`.
+    pub fn reflection_columns(&self, differences: usize) -> Vec<usize> {
+        return (1..self.width)
+            .filter(|&x| {
+                let mut count = 0;
+                for i in 0..x.min(self.width - x) {
+                    let a = self.column(x - 1 - i);
+                    count += a.zip(self.column(x + i)).filter(|(a, b)| a != b).count();
+                }
+                count == differences
+            })
+            .collect();
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Point) -> &Self::Output {
+        return self
+            .get(index)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", index));
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        return self
+            .get_mut(index)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", index));
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = RaggedRow;
+
+    /// One row per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Grid::from_rows(s.lines().map(|x| x.chars().collect()).collect());
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        return text.parse().unwrap();
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let sut = grid("abc\ndef");
+        assert_eq!((sut.width(), sut.height()), (3, 2));
+        assert_eq!(sut[Point::new(2, 1)], 'f');
+        assert_eq!(sut.get(Point::new(3, 0)), None);
+        assert_eq!(sut.get(Point::new(0, -1)), None);
+        assert_eq!(sut.column(1).collect::<String>(), "be");
+        assert_eq!(sut.iter().nth(4), Some((Point::new(1, 1), &'e')));
+        assert_eq!(sut.to_string(), "abc\ndef\n");
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(RaggedRow {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn connected_cells() {
+        let sut = grid("#.#\n#.#\n..#");
+        let res = sut.components(|x| *x == '#', &Direction::ORTHOGONAL);
+        assert_eq!(
+            res,
+            vec![
+                vec![Point::new(0, 0), Point::new(0, 1)],
+                vec![Point::new(2, 0), Point::new(2, 1), Point::new(2, 2)],
+            ]
+        );
+        assert_eq!(sut.components(|x| *x == '.', &Direction::ALL).len(), 1);
+    }
+
+    #[test]
+    fn transforms() {
+        let sut = grid("abc\ndef");
+        assert_eq!(sut.transpose(), grid("ad\nbe\ncf"));
+        assert_eq!(sut.rotate_right(), grid("da\neb\nfc"));
+        assert_eq!(sut.rotate_left(), grid("cf\nbe\nad"));
+        assert_eq!(sut.flip_horizontal(), grid("cba\nfed"));
+        assert_eq!(sut.flip_vertical(), grid("def\nabc"));
+        assert_eq!(
+            sut.rotate_right().rotate_right(),
+            sut.flip_horizontal().flip_vertical()
+        );
+        assert_eq!(
+            sut.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            sut
+        );
+        assert_eq!(sut.rotate_left().rotate_right(), sut);
+        assert_eq!(sut.transpose().transpose(), sut);
+    }
+
+    #[test]
+    fn empty_grid() {
+        let mut sut: Grid<char> = grid("");
+        assert_eq!(sut.bounds(), None);
+        assert_eq!(sut.transpose(), sut);
+        sut.insert_row(0, vec!['a', 'b']);
+        assert_eq!(sut, grid("ab"));
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut sut = grid("abc\ndef");
+        sut.insert_row(1, vec!['x', 'y', 'z']);
+        assert_eq!(sut, grid("abc\nxyz\ndef"));
+        sut.insert_column(3, vec!['1', '2', '3']);
+        assert_eq!(sut, grid("abc1\nxyz2\ndef3"));
+        sut.insert_column(0, vec!['4', '5', '6']);
+        assert_eq!(sut, grid("4abc1\n5xyz2\n6def3"));
+        assert_eq!(sut.remove_column(2), vec!['b', 'y', 'e']);
+        assert_eq!(sut.remove_row(0), vec!['4', 'a', 'c', '1']);
+        assert_eq!(sut, grid("5xz2\n6df3"));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn insert_wrong_length() {
+        grid("ab\ncd").insert_row(0, vec!['x']);
+    }
+
+    #[test]
+    fn tilt_rolls_until_blocked() {
+        let mut sut = grid("O.#.\n..O.\nO#..\n.O.O");
+        sut.tilt(Direction::North, |x| *x == 'O', |x| *x == '.');
+        assert_eq!(sut, grid("O.#O\nO.O.\n.#..\n.O.."));
+        sut.tilt(Direction::East, |x| *x == 'O', |x| *x == '.');
+        assert_eq!(sut, grid(".O#O\n..OO\n.#..\n...O"));
+        sut.tilt(Direction::South, |x| *x == 'O', |x| *x == '.');
+        assert_eq!(sut, grid("..#.\n.O.O\n.#.O\n..OO"));
+        sut.tilt(Direction::West, |x| *x == 'O', |x| *x == '.');
+        assert_eq!(sut, grid("..#.\nOO..\n.#O.\nOO.."));
+    }
+
+    #[test]
+    fn reflections() {
+        let sut = grid(
+            "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.",
+        );
+        assert_eq!(sut.reflection_columns(0), vec![5]);
+        assert_eq!(sut.reflection_rows(0), vec![]);
+        assert_eq!(sut.reflection_rows(1), vec![3]);
+
+        let sut = grid(
+            "\
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#",
+        );
+        assert_eq!(sut.reflection_rows(0), vec![4]);
+        assert_eq!(sut.reflection_rows(1), vec![1]);
+        assert_eq!(sut.transpose().reflection_columns(0), vec![4]);
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt::Debug;
+use std::ops::{Add, Range, Sub};
+
+/// Integer types an `Interval` can be made of.
+pub trait Endpoint: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! endpoint {
+    ($($t:ty),*) => {
+        $(
+            impl Endpoint for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    return <$t>::checked_add(self, other);
+                }
+            }
+        )*
+    };
+}
+
+endpoint!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The integers from `start` up to but not including `end`, empty when
+/// `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    pub fn new(start: T, end: T) -> Interval<T> {
+        return Interval { start, end };
+    }
+
+    /// The integers from `first` up to and including `last`, `None` when
+    /// `last` is the largest value of `T` and the end cannot be represented.
+    pub fn inclusive(first: T, last: T) -> Option<Interval<T>> {
+        return Some(Interval {
+            start: first,
+            end: last.checked_add(T::ONE)?,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.end <= self.start;
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            return T::ZERO;
+        }
+        return self.end - self.start;
+    }
+
+    /// The largest integer in the interval.
+    pub fn last(&self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        return Some(self.end - T::ONE);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        return self.start <= value && value < self.end;
+    }
+
+    pub fn range(&self) -> Range<T> {
+        return self.start..self.end;
+    }
+
+    /// How far `value` lies outside the interval, 0 when it is inside.
+    pub fn distance_to(&self, value: T) -> T {
+        if value < self.start {
+            return self.start - value;
+        }
+        if value >= self.end {
+            return value - self.end + T::ONE;
+        }
+        return T::ZERO;
+    }
+
+    /// The interval extended by `amount` on both sides.
+    pub fn grow(&self, amount: T) -> Interval<T> {
+        return Interval {
+            start: self.start - amount,
+            end: self.end + amount,
+        };
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        return self.intersect(other).is_some();
+    }
+
+    /// Whether the intervals overlap or `other` starts right where this one
+    /// ends or the other way around.
+    pub fn touches(&self, other: &Interval<T>) -> bool {
+        return !self.is_empty()
+            && !other.is_empty()
+            && self.start <= other.end
+            && other.start <= self.end;
+    }
+
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let res = Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        };
+        if res.is_empty() {
+            return None;
+        }
+        return Some(res);
+    }
+
+    /// The union of both intervals when it is a single interval.
+    pub fn merge(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if !self.touches(other) {
+            return None;
+        }
+        return Some(Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        });
+    }
+
+    /// The parts below `at` and from `at` on, either may be empty.
+    pub fn split_at(&self, at: T) -> (Interval<T>, Interval<T>) {
+        let at = at.max(self.start).min(self.end.max(self.start));
+        return (
+            Interval {
+                start: self.start,
+                end: at,
+            },
+            Interval {
+                start: at,
+                end: self.end,
+            },
+        );
+    }
+
+    /// The non-empty parts of this interval outside `other`, at most two.
+    pub fn subtract(&self, other: &Interval<T>) -> Vec<Interval<T>> {
+        if !self.overlaps(other) {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+        let (below, _) = self.split_at(other.start);
+        let (_, above) = self.split_at(other.end);
+        return [below, above]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect();
+    }
+}
+
+impl<T> Interval<T> {
+    /// Converts both ends, such as to a signed type before growing.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Interval<U> {
+        return Interval {
+            start: f(self.start),
+            end: f(self.end),
+        };
+    }
+}
+
+/// A set of integers stored as sorted intervals that neither overlap nor
+/// touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Endpoint> RangeSet<T> {
+    pub fn new() -> RangeSet<T> {
+        return RangeSet { intervals: vec![] };
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        return &self.intervals;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.intervals.is_empty();
+    }
+
+    /// Number of integers in the set.
+    pub fn covered(&self) -> T {
+        return self.intervals.iter().fold(T::ZERO, |x, y| x + y.len());
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|x| x.end <= value);
+        return self.intervals.get(i).is_some_and(|x| x.contains(value));
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let from = self.intervals.partition_point(|x| x.end < interval.start);
+        let to = self.intervals.partition_point(|x| x.start <= interval.end);
+        let merged = self.intervals[from..to]
+            .iter()
+            .fold(interval, |x, y| x.merge(y).unwrap());
+        self.intervals.splice(from..to, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let from = self.intervals.partition_point(|x| x.end <= interval.start);
+        let to = self.intervals.partition_point(|x| x.start < interval.end);
+        let rest: Vec<Interval<T>> = self.intervals[from..to]
+            .iter()
+            .flat_map(|x| x.subtract(&interval))
+            .collect();
+        self.intervals.splice(from..to, rest);
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut res = self.clone();
+        for interval in &other.intervals {
+            res.insert(*interval);
+        }
+        return res;
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut res = RangeSet::new();
+        for a in &self.intervals {
+            for b in &other.intervals {
+                if let Some(x) = a.intersect(b) {
+                    res.insert(x);
+                }
+            }
+        }
+        return res;
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut res = self.clone();
+        for interval in &other.intervals {
+            res.remove(*interval);
+        }
+        return res;
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut res = RangeSet::new();
+        for interval in iter {
+            res.insert(interval);
+        }
+        return res;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i32, i32)]) -> RangeSet<i32> {
+        return intervals.iter().map(|x| Interval::new(x.0, x.1)).collect();
+    }
+
+    fn pairs(set: &RangeSet<i32>) -> Vec<(i32, i32)> {
+        return set.intervals().iter().map(|x| (x.start, x.end)).collect();
+    }
+
+    #[test]
+    fn inclusive_and_exclusive() {
+        let sut = Interval::inclusive(3, 5).unwrap();
+        assert_eq!(sut, Interval::new(3, 6));
+        assert_eq!(sut.len(), 3);
+        assert_eq!(sut.last(), Some(5));
+        assert!(sut.contains(5));
+        assert!(!sut.contains(6));
+        assert_eq!(sut.range().collect::<Vec<i32>>(), vec![3, 4, 5]);
+        assert!(Interval::new(4, 4).is_empty());
+        assert_eq!(Interval::new(4, 2).len(), 0);
+        assert_eq!(Interval::new(4, 2).last(), None);
+    }
+
+    #[test]
+    fn inclusive_up_to_max() {
+        assert_eq!(Interval::inclusive(0u8, 254), Some(Interval::new(0, 255)));
+        assert_eq!(Interval::inclusive(0u8, 255), None);
+        assert_eq!(Interval::inclusive(i64::MIN, i64::MAX), None);
+    }
+
+    #[test]
+    fn distance_and_grow() {
+        let sut = Interval::inclusive(3i64, 5).unwrap();
+        assert_eq!(sut.distance_to(1), 2);
+        assert_eq!(sut.distance_to(4), 0);
+        assert_eq!(sut.distance_to(6), 1);
+        assert_eq!(Some(sut.grow(1)), Interval::inclusive(2, 6));
+        assert_eq!(
+            Interval::new(1u8, 4).map(i64::from).grow(2),
+            Interval::new(-1, 6)
+        );
+    }
+
+    #[test]
+    fn intersect_and_merge() {
+        let a = Interval::new(0, 5);
+        let b = Interval::new(3, 8);
+        let c = Interval::new(5, 7);
+        let d = Interval::new(6, 9);
+        assert_eq!(a.intersect(&b), Some(Interval::new(3, 5)));
+        assert_eq!(a.intersect(&c), None);
+        assert!(!a.overlaps(&c));
+        assert!(a.touches(&c));
+        assert_eq!(a.merge(&c), Some(Interval::new(0, 7)));
+        assert_eq!(a.merge(&d), None);
+        assert_eq!(a.merge(&Interval::new(9, 9)), Some(a));
+    }
+
+    #[test]
+    fn split_and_subtract() {
+        let sut = Interval::new(0, 10);
+        assert_eq!(sut.split_at(4), (Interval::new(0, 4), Interval::new(4, 10)));
+        assert_eq!(sut.split_at(-3), (Interval::new(0, 0), sut));
+        assert_eq!(sut.split_at(12), (sut, Interval::new(10, 10)));
+        assert_eq!(
+            sut.subtract(&Interval::new(3, 5)),
+            vec![Interval::new(0, 3), Interval::new(5, 10)]
+        );
+        assert_eq!(
+            sut.subtract(&Interval::new(-1, 5)),
+            vec![Interval::new(5, 10)]
+        );
+        assert_eq!(sut.subtract(&Interval::new(-1, 11)), vec![]);
+        assert_eq!(sut.subtract(&Interval::new(10, 11)), vec![sut]);
+    }
+
+    #[test]
+    fn range_set_insert_merges() {
+        let mut sut = set(&[(0, 2), (5, 7), (10, 12)]);
+        assert_eq!(pairs(&sut), vec![(0, 2), (5, 7), (10, 12)]);
+        sut.insert(Interval::new(2, 5));
+        assert_eq!(pairs(&sut), vec![(0, 7), (10, 12)]);
+        sut.insert(Interval::new(8, 9));
+        sut.insert(Interval::new(-5, -5));
+        assert_eq!(pairs(&sut), vec![(0, 7), (8, 9), (10, 12)]);
+        sut.insert(Interval::new(-1, 20));
+        assert_eq!(pairs(&sut), vec![(-1, 20)]);
+        assert_eq!(sut.covered(), 21);
+    }
+
+    #[test]
+    fn range_set_remove_splits() {
+        let mut sut = set(&[(0, 10), (20, 30)]);
+        sut.remove(Interval::new(5, 25));
+        assert_eq!(pairs(&sut), vec![(0, 5), (25, 30)]);
+        sut.remove(Interval::new(26, 27));
+        assert_eq!(pairs(&sut), vec![(0, 5), (25, 26), (27, 30)]);
+        assert!(sut.contains(4));
+        assert!(!sut.contains(5));
+        assert!(!sut.contains(26));
+        assert!(sut.contains(27));
+        sut.remove(Interval::new(-100, 100));
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn range_set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (14, 20)]);
+        assert_eq!(pairs(&a.union(&b)), vec![(0, 20)]);
+        assert_eq!(pairs(&a.intersection(&b)), vec![(3, 5), (10, 12), (14, 15)]);
+        assert_eq!(pairs(&a.difference(&b)), vec![(0, 3), (12, 14)]);
+    }
+
+    #[test]
+    fn range_set_matches_brute_force() {
+        let operations = [
+            (3, 9, true),
+            (0, 2, true),
+            (5, 6, false),
+            (8, 15, true),
+            (1, 4, false),
+        ];
+        let mut sut = RangeSet::new();
+        let mut expected = [false; 20];
+        for (start, end, insert) in operations {
+            if insert {
+                sut.insert(Interval::new(start, end));
+            } else {
+                sut.remove(Interval::new(start, end));
+            }
+            for x in start..end {
+                expected[x as usize] = insert;
+            }
+            for (x, contained) in expected.iter().enumerate() {
+                assert_eq!(sut.contains(x as i32), *contained);
+            }
+            let intervals = sut.intervals();
+            for pair in intervals.windows(2) {
+                assert!(pair[0].end < pair[1].start);
+            }
+        }
+    }
+}
@@ -1 +1,9 @@
+pub mod cycle;
+pub mod dsu;
+pub mod geom;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod scan;
+pub mod search;
 pub mod trie;
@@ -0,0 +1,267 @@
+use std::fmt::Debug;
+
+/// Integers of at most 64 bits, so every intermediate result of the
+/// functions below fits in an `i128` or `u128`.
+pub trait Integer: Copy + Ord + Debug {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn to_i128(self) -> i128 {
+                    return self as i128;
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    return Self::try_from(value).ok();
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Greatest common divisor, never negative, `gcd(0, 0)` is 0.
+///
+/// Panics when the result does not fit in `T`, which only happens for
+/// `gcd(i64::MIN, 0)` and the like.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let res = gcd_i128(a.to_i128(), b.to_i128());
+    return T::from_i128(res).unwrap_or_else(|| panic!("gcd {} does not fit", res));
+}
+
+fn gcd_i128(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    return a;
+}
+
+/// Least common multiple, never negative, `None` when it does not fit in `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let (a, b) = (a.to_i128(), b.to_i128());
+    if a == 0 || b == 0 {
+        return T::from_i128(0);
+    }
+    return T::from_i128((a / gcd_i128(a, b)).checked_mul(b)?.abs());
+}
+
+/// `base` to the power `exponent` modulo `modulus`, in `0..modulus`.
+///
+/// Panics if `modulus` is not positive.
+pub fn mod_pow<T: Integer>(base: T, exponent: u64, modulus: T) -> T {
+    let modulus = positive_modulus(modulus);
+    let mut base = reduce(base.to_i128(), modulus);
+    let mut exponent = exponent;
+    let mut res = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            res = mul_mod(res, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    return from_u128(res).unwrap();
+}
+
+/// The `x` in `0..modulus` for which `a * x` is 1 modulo `modulus`, `None`
+/// when `a` and `modulus` share a factor.
+///
+/// Panics if `modulus` is not positive.
+pub fn mod_inv<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let modulus = positive_modulus(modulus);
+    let res = inverse(reduce(a.to_i128(), modulus), modulus)?;
+    return from_u128(res);
+}
+
+/// Solves `x ≡ r (mod m)` for every `(r, m)`, giving the smallest
+/// non-negative `x` and the modulus of the combined congruence. The moduli
+/// need not be coprime.
+///
+/// `None` when the congruences contradict each other or the combined modulus
+/// does not fit in `T`. Panics if a modulus is not positive.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut residue: u128 = 0;
+    let mut modulus: u128 = 1;
+
+    for (r, m) in congruences {
+        let m = positive_modulus(*m);
+        let r = reduce(r.to_i128(), m);
+
+        let g = gcd_i128(modulus as i128, m as i128) as u128;
+        let difference = (r + m - residue % m) % m;
+        if !difference.is_multiple_of(g) {
+            return None;
+        }
+
+        // Solve residue + modulus * k ≡ r (mod m) for k modulo m / g.
+        let step = m / g;
+        let k = mul_mod(
+            (difference / g) % step,
+            inverse((modulus / g) % step, step).unwrap(),
+            step,
+        );
+        let combined = modulus.checked_mul(step)?;
+        // The combined modulus only grows, so stop as soon as it does not fit.
+        from_u128::<T>(combined)?;
+        residue = (residue + modulus * k) % combined;
+        modulus = combined;
+    }
+
+    return Some((from_u128(residue)?, from_u128(modulus)?));
+}
+
+fn from_u128<T: Integer>(value: u128) -> Option<T> {
+    return T::from_i128(i128::try_from(value).ok()?);
+}
+
+fn positive_modulus<T: Integer>(modulus: T) -> u128 {
+    let modulus = modulus.to_i128();
+    if modulus <= 0 {
+        panic!("Modulus {} is not positive", modulus);
+    }
+    return modulus as u128;
+}
+
+fn reduce(value: i128, modulus: u128) -> u128 {
+    return value.rem_euclid(modulus as i128) as u128;
+}
+
+/// Both factors are below `modulus`, which is at most 64 bits wide, so the
+/// product fits in a `u128`.
+fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    return a * b % modulus;
+}
+
+/// Extended Euclid on `a` in `0..modulus`.
+fn inverse(a: u128, modulus: u128) -> Option<u128> {
+    let (mut old_r, mut r) = (a as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    if old_r != 1 {
+        // Every number is its own inverse modulo 1.
+        return if modulus == 1 { Some(0) } else { None };
+    }
+    return Some(reduce(old_s, modulus));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Largest prime below 2^64.
+    const PRIME: u64 = 18446744073709551557;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4i64, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(200u8, 3), None);
+        assert_eq!(
+            lcm(u32::MAX as u64, u32::MAX as u64 - 1),
+            Some(18446744060824649730)
+        );
+        assert_eq!(lcm(PRIME, PRIME - 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn gcd_overflow() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn mod_pow_small() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2i32, 3, 5), 2);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+    }
+
+    #[test]
+    fn mod_pow_large_modulus() {
+        // Fermat: a^(p - 1) is 1 modulo a prime p.
+        assert_eq!(mod_pow(u64::MAX - 1, PRIME - 1, PRIME), 1);
+        assert_eq!(mod_pow(3u64, PRIME, PRIME), 3);
+    }
+
+    #[test]
+    fn mod_inv_values() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3i32, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(mod_inv(5, 1), Some(0));
+        let inv = mod_inv(PRIME - 2, PRIME).unwrap();
+        assert_eq!(mod_pow(PRIME - 2, PRIME - 2, PRIME), inv);
+    }
+
+    #[test]
+    fn mod_inv_exhaustive() {
+        for modulus in 1..30i32 {
+            for a in -30..30i32 {
+                let expected = (0..modulus).find(|x| (a * x).rem_euclid(modulus) == 1 % modulus);
+                assert_eq!(mod_inv(a, modulus), expected, "{} mod {}", a, modulus);
+            }
+        }
+    }
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1i64, 4), (0, 9)]), Some((27, 36)));
+        assert_eq!(crt::<u32>(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_shared_factors() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn crt_exhaustive() {
+        for a in 1..10u32 {
+            for b in 1..10u32 {
+                for r in 0..a {
+                    for s in 0..b {
+                        let modulus = lcm(a, b).unwrap();
+                        let expected = (0..modulus).find(|x| x % a == r && x % b == s);
+                        assert_eq!(
+                            crt(&[(r, a), (s, b)]),
+                            expected.map(|x| (x, modulus)),
+                            "{} mod {}, {} mod {}",
+                            r,
+                            a,
+                            s,
+                            b
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crt_large_moduli() {
+        let res = crt(&[(1, PRIME), (0, 2)]).map(|(x, m)| (x as u128, m as u128));
+        assert_eq!(res, None);
+        let res = crt(&[(5u64, 4294967291), (7, 4294967279)]).unwrap();
+        assert_eq!(res.0 % 4294967291, 5);
+        assert_eq!(res.0 % 4294967279, 7);
+        assert_eq!(res.1, 4294967291 * 4294967279);
+    }
+}
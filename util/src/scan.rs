@@ -0,0 +1,433 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Byte range within the scanned text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Inclusive
+    pub start: usize,
+    /// Exclusive
+    pub end: usize,
+}
+
+impl Span {
+    pub fn empty(at: usize) -> Span {
+        return Span { start: at, end: at };
+    }
+}
+
+/// 1-based line and column, the column counts chars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanErrorKind {
+    /// `found` is the run of digits, run of letters or single char at the
+    /// position.
+    Unexpected {
+        expected: String,
+        found: String,
+    },
+    UnexpectedEnd {
+        expected: String,
+    },
+    /// The number does not fit the type it is read as.
+    NumberOutOfRange,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError {
+    pub span: Span,
+    pub position: Position,
+    pub kind: ScanErrorKind,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: ",
+            self.position.line, self.position.column
+        )?;
+        return match &self.kind {
+            ScanErrorKind::Unexpected { expected, found } => {
+                write!(f, "expected {}, found '{}'", expected, found)
+            }
+            ScanErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "expected {}, found end of text", expected)
+            }
+            ScanErrorKind::NumberOutOfRange => write!(f, "number is out of range"),
+        };
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Reads tokens from the front of a text. Whitespace between tokens is
+/// skipped right after each token, so tokens need not be separated by it.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(text: &'a str) -> Scanner<'a> {
+        let mut res = Scanner { text, offset: 0 };
+        res.skip_whitespace();
+        return res;
+    }
+
+    /// Byte offset of the next token.
+    pub fn offset(&self) -> usize {
+        return self.offset;
+    }
+
+    /// Everything from the next token on.
+    pub fn rest(&self) -> &'a str {
+        return &self.text[self.offset..];
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        return self.rest().is_empty();
+    }
+
+    /// First char of the next token.
+    pub fn peek(&self) -> Option<char> {
+        return self.rest().chars().next();
+    }
+
+    /// Line and column of a byte offset.
+    pub fn position(&self, offset: usize) -> Position {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map(|x| x + 1).unwrap_or(0);
+        return Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        };
+    }
+
+    /// Consumes `literal` if it comes next.
+    pub fn eat(&mut self, literal: &str) -> Option<Span> {
+        if !self.rest().starts_with(literal) {
+            return None;
+        }
+        return Some(self.advance(literal.len()));
+    }
+
+    pub fn literal(&mut self, literal: &str) -> Result<Span, ScanError> {
+        return self
+            .eat(literal)
+            .ok_or_else(|| self.unexpected(format!("'{}'", literal)));
+    }
+
+    /// A run of letters.
+    pub fn word(&mut self) -> Result<Token<'a>, ScanError> {
+        let len = run_length(self.rest(), |_, x| x.is_alphabetic());
+        return self.token(len, "a word");
+    }
+
+    /// A letter or `_` followed by letters, digits and `_`.
+    pub fn identifier(&mut self) -> Result<Token<'a>, ScanError> {
+        let len = run_length(self.rest(), |i, x| {
+            x == '_' || x.is_alphabetic() || (i > 0 && x.is_alphanumeric())
+        });
+        return self.token(len, "an identifier");
+    }
+
+    /// A run of digits.
+    pub fn unsigned<T: FromStr>(&mut self) -> Result<(T, Span), ScanError> {
+        let len = run_length(self.rest(), |_, x| x.is_ascii_digit());
+        return self.number(len, "a number");
+    }
+
+    /// A run of digits, optionally preceded by `-` or `+`.
+    pub fn integer<T: FromStr>(&mut self) -> Result<(T, Span), ScanError> {
+        let rest = self.rest();
+        let sign = if rest.starts_with(['-', '+']) { 1 } else { 0 };
+        let digits = run_length(&rest[sign..], |_, x| x.is_ascii_digit());
+        let len = if digits == 0 { 0 } else { sign + digits };
+        return self.number(len, "an integer");
+    }
+
+    /// One or more items with `separator` between them. After each separator
+    /// `continues` decides whether another item follows, which allows a
+    /// trailing separator.
+    pub fn separated<T, E>(
+        &mut self,
+        separator: &str,
+        continues: impl Fn(&Scanner<'a>) -> bool,
+        mut item: impl FnMut(&mut Scanner<'a>) -> Result<T, E>,
+    ) -> Result<Vec<T>, E> {
+        let mut res = vec![item(self)?];
+        while self.eat(separator).is_some() {
+            if !continues(self) {
+                break;
+            }
+            res.push(item(self)?);
+        }
+        return Ok(res);
+    }
+
+    pub fn end(&mut self) -> Result<(), ScanError> {
+        if self.is_at_end() {
+            return Ok(());
+        }
+        return Err(self.unexpected("end of text"));
+    }
+
+    /// Error for finding something else than `expected` at the next token.
+    pub fn unexpected(&self, expected: impl Into<String>) -> ScanError {
+        let expected = expected.into();
+        let rest = self.rest();
+        let Some(first) = rest.chars().next() else {
+            return self.error(
+                Span::empty(self.offset),
+                ScanErrorKind::UnexpectedEnd { expected },
+            );
+        };
+
+        let len = if first.is_ascii_digit() {
+            run_length(rest, |_, x| x.is_ascii_digit())
+        } else if first.is_alphabetic() {
+            run_length(rest, |_, x| x.is_alphabetic())
+        } else {
+            first.len_utf8()
+        };
+        let span = Span {
+            start: self.offset,
+            end: self.offset + len,
+        };
+        return self.error(
+            span,
+            ScanErrorKind::Unexpected {
+                expected,
+                found: rest[..len].to_string(),
+            },
+        );
+    }
+
+    fn error(&self, span: Span, kind: ScanErrorKind) -> ScanError {
+        return ScanError {
+            span,
+            position: self.position(span.start),
+            kind,
+        };
+    }
+
+    fn token(&mut self, len: usize, expected: &str) -> Result<Token<'a>, ScanError> {
+        if len == 0 {
+            return Err(self.unexpected(expected));
+        }
+        let text = &self.rest()[..len];
+        return Ok(Token {
+            text,
+            span: self.advance(len),
+        });
+    }
+
+    fn number<T: FromStr>(&mut self, len: usize, expected: &str) -> Result<(T, Span), ScanError> {
+        let token = self.token(len, expected)?;
+        let value = token
+            .text
+            .parse()
+            .map_err(|_| self.error(token.span, ScanErrorKind::NumberOutOfRange))?;
+        return Ok((value, token.span));
+    }
+
+    /// Consumes `len` bytes and the whitespace after them.
+    fn advance(&mut self, len: usize) -> Span {
+        let span = Span {
+            start: self.offset,
+            end: self.offset + len,
+        };
+        self.offset = span.end;
+        self.skip_whitespace();
+        return span;
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.offset..];
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+}
+
+/// Byte length of the leading chars passing `matches`, which gets each
+/// char's index among them.
+fn run_length(text: &str, matches: impl Fn(usize, char) -> bool) -> usize {
+    return text
+        .chars()
+        .enumerate()
+        .take_while(|(i, x)| matches(*i, *x))
+        .map(|(_, x)| x.len_utf8())
+        .sum();
+}
+
+/// A group of lines between blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// 1-based line number of the first line.
+    pub line: usize,
+    /// The lines of the section, without the final line break.
+    pub text: &'a str,
+}
+
+/// Splits `text` on lines holding only whitespace.
+pub fn sections(text: &str) -> Vec<Section<'_>> {
+    let mut res = vec![];
+    // Start offset and line number of the section being read.
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((start, number)) = current.take() {
+                res.push(section(text, start, offset, number));
+            }
+        } else if current.is_none() {
+            current = Some((offset, i + 1));
+        }
+        offset += line.len();
+    }
+    if let Some((start, number)) = current {
+        res.push(section(text, start, offset, number));
+    }
+    return res;
+}
+
+fn section(text: &str, start: usize, end: usize, line: usize) -> Section<'_> {
+    let text = &text[start..end];
+    return Section {
+        line,
+        text: text
+            .strip_suffix('\n')
+            .map(|x| x.strip_suffix('\r').unwrap_or(x))
+            .unwrap_or(text),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_tokens() {
+        let mut sut = Scanner::new("  move_2 -17 to x:+4, 5");
+        assert_eq!(sut.identifier().unwrap().text, "move_2");
+        assert_eq!(
+            sut.integer::<i32>().unwrap(),
+            (-17, Span { start: 9, end: 12 })
+        );
+        assert_eq!(sut.word().unwrap().text, "to");
+        assert_eq!(sut.word().unwrap().span, Span { start: 16, end: 17 });
+        assert_eq!(sut.literal(":").unwrap(), Span { start: 17, end: 18 });
+        assert_eq!(sut.integer::<i64>().unwrap().0, 4);
+        assert!(sut.eat(";").is_none());
+        assert_eq!(sut.eat(","), Some(Span { start: 20, end: 21 }));
+        assert_eq!(sut.peek(), Some('5'));
+        assert_eq!(sut.unsigned::<u8>().unwrap().0, 5);
+        assert!(sut.end().is_ok());
+    }
+
+    #[test]
+    fn tokens_need_no_whitespace() {
+        let mut sut = Scanner::new("abc12def");
+        assert_eq!(sut.word().unwrap().text, "abc");
+        assert_eq!(sut.unsigned::<u32>().unwrap().0, 12);
+        assert_eq!(sut.rest(), "def");
+    }
+
+    #[test]
+    fn unexpected_token() {
+        let mut sut = Scanner::new("Game\n  x12 ");
+        sut.word().unwrap();
+        let res = sut.unsigned::<u32>().unwrap_err();
+        assert_eq!(res.span, Span { start: 7, end: 8 });
+        assert_eq!(res.position, Position { line: 2, column: 3 });
+        assert_eq!(
+            res.kind,
+            ScanErrorKind::Unexpected {
+                expected: "a number".to_string(),
+                found: "x".to_string()
+            }
+        );
+        assert_eq!(
+            res.to_string(),
+            "line 2, column 3: expected a number, found 'x'"
+        );
+    }
+
+    #[test]
+    fn unexpected_end() {
+        let mut sut = Scanner::new("a ");
+        sut.word().unwrap();
+        let res = sut.literal("=").unwrap_err();
+        assert_eq!(res.span, Span::empty(2));
+        assert_eq!(
+            res.kind,
+            ScanErrorKind::UnexpectedEnd {
+                expected: "'='".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(
+            Scanner::new("300").unsigned::<u8>().unwrap_err().kind,
+            ScanErrorKind::NumberOutOfRange
+        );
+        let res = Scanner::new("-200").integer::<i8>().unwrap_err();
+        assert_eq!(res.kind, ScanErrorKind::NumberOutOfRange);
+        assert_eq!(res.to_string(), "line 1, column 1: number is out of range");
+        assert_eq!(Scanner::new("-128").integer::<i8>().unwrap().0, -128);
+        assert!(Scanner::new("-x").integer::<i8>().is_err());
+        assert!(Scanner::new("-3").unsigned::<u8>().is_err());
+        let res = Scanner::new("99999999999").unsigned::<u32>().unwrap_err();
+        assert_eq!(res.span, Span { start: 0, end: 11 });
+    }
+
+    #[test]
+    fn separated_lists() {
+        let mut sut = Scanner::new("1, 2,3,; 4");
+        let res = sut
+            .separated(
+                ",",
+                |x| x.peek().is_some_and(|c| c.is_ascii_digit()),
+                |x| x.unsigned::<u32>().map(|x| x.0),
+            )
+            .unwrap();
+        assert_eq!(res, vec![1, 2, 3]);
+        assert_eq!(sut.rest(), "; 4");
+
+        let mut sut = Scanner::new("1, x");
+        let res = sut.separated(",", |_| true, |x| x.unsigned::<u32>());
+        assert_eq!(res.unwrap_err().span, Span { start: 3, end: 4 });
+    }
+
+    #[test]
+    fn split_sections() {
+        let text = "\na\nb\n\n  \nc\r\n\r\nd";
+        assert_eq!(
+            sections(text),
+            vec![
+                Section {
+                    line: 2,
+                    text: "a\nb"
+                },
+                Section { line: 6, text: "c" },
+                Section { line: 8, text: "d" },
+            ]
+        );
+        assert_eq!(sections(""), vec![]);
+        assert_eq!(sections("x\n"), vec![Section { line: 1, text: "x" }]);
+    }
+}
@@ -0,0 +1,317 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Cost of a step for `dijkstra` and `astar`.
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! cost {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+cost!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A cheapest path, `nodes` starts with the start and ends with the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Position in a grid as row and column.
+pub type Position = (usize, usize);
+
+/// Breadth-first search for the path with the fewest steps to a node for
+/// which `is_goal` holds.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let nodes = reconstruct(&parents, node);
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+        for next in neighbours(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Cheapest path to a node for which `is_goal` holds, `neighbours` yields
+/// every next node with the cost of the step there. Costs may not be
+/// negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    return astar(start, neighbours, |_| C::ZERO, is_goal);
+}
+
+/// Like `dijkstra`, but explores nodes in order of their cost plus
+/// `heuristic`, an estimate of the remaining cost. The path is only the
+/// cheapest when the estimate never exceeds the actual remaining cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes are stored once and referred to by index, so they need no ordering.
+    let mut nodes: Vec<N> = vec![start.clone()];
+    let mut indices: HashMap<N, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut costs: Vec<C> = vec![C::ZERO];
+    let mut parents: Vec<Option<usize>> = vec![None];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+
+    while let Some(Reverse((estimate, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if estimate > costs[index] + heuristic(&node) {
+            continue;
+        }
+        if is_goal(&node) {
+            let mut path = vec![index];
+            while let Some(parent) = parents[*path.last().unwrap()] {
+                path.push(parent);
+            }
+            return Some(Path {
+                nodes: path.into_iter().rev().map(|x| nodes[x].clone()).collect(),
+                cost: costs[index],
+            });
+        }
+
+        for (next, step) in neighbours(&node) {
+            let cost = costs[index] + step;
+            let next_index = match indices.get(&next) {
+                Some(&next_index) if costs[next_index] <= cost => continue,
+                Some(&next_index) => next_index,
+                None => {
+                    indices.insert(next.clone(), nodes.len());
+                    nodes.push(next.clone());
+                    costs.push(cost);
+                    parents.push(None);
+                    nodes.len() - 1
+                }
+            };
+            costs[next_index] = cost;
+            parents[next_index] = Some(index);
+            queue.push(Reverse((cost + heuristic(&next), next_index)));
+        }
+    }
+
+    return None;
+}
+
+fn reconstruct<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> {
+    let mut res = vec![goal];
+    while let Some(Some(parent)) = parents.get(res.last().unwrap()) {
+        res.push(parent.clone());
+    }
+    res.reverse();
+    return res;
+}
+
+/// The horizontal and vertical neighbours of `position` whose cell passes
+/// `is_open`. Rows may differ in length, cells past the end of a row do not
+/// exist.
+pub fn grid_neighbours(
+    grid: &[Vec<char>],
+    position: Position,
+    is_open: impl Fn(char) -> bool,
+) -> Vec<Position> {
+    let (row, column) = position;
+    let candidates = [
+        row.checked_sub(1).map(|x| (x, column)),
+        Some((row + 1, column)),
+        column.checked_sub(1).map(|x| (row, x)),
+        Some((row, column + 1)),
+    ];
+    return candidates
+        .into_iter()
+        .flatten()
+        .filter(|&(row, column)| {
+            grid.get(row)
+                .and_then(|x| x.get(column))
+                .is_some_and(|x| is_open(*x))
+        })
+        .collect();
+}
+
+/// Position of the first cell holding `c`, reading row by row.
+pub fn find_in_grid(grid: &[Vec<char>], c: char) -> Option<Position> {
+    for (row, line) in grid.iter().enumerate() {
+        if let Some(column) = line.iter().position(|x| *x == c) {
+            return Some((row, column));
+        }
+    }
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Vec<Vec<char>> {
+        return text.lines().map(|x| x.chars().collect()).collect();
+    }
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    fn manhattan(a: Position, b: Position) -> usize {
+        return a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+    }
+
+    #[test]
+    fn bfs_shortest_maze_path() {
+        let maze = grid(MAZE);
+        let start = find_in_grid(&maze, 'S').unwrap();
+        let end = find_in_grid(&maze, 'E').unwrap();
+        let res = bfs(
+            start,
+            |&x| grid_neighbours(&maze, x, |c| c != '#'),
+            |&x| x == end,
+        )
+        .unwrap();
+
+        assert_eq!(res.cost, 15);
+        assert_eq!(res.nodes.len(), 16);
+        assert_eq!(res.nodes[0], start);
+        assert_eq!(*res.nodes.last().unwrap(), end);
+        for pair in res.nodes.windows(2) {
+            assert_eq!(manhattan(pair[0], pair[1]), 1);
+            assert_ne!(maze[pair[1].0][pair[1].1], '#');
+        }
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let maze = grid("S.#\n###\n..E");
+        let end = find_in_grid(&maze, 'E').unwrap();
+        let open = |x: &Position| grid_neighbours(&maze, *x, |c| c != '#');
+        assert_eq!(bfs((0, 0), open, |&x| x == end), None);
+        let weighted = |x: &Position| open(x).into_iter().map(|x| (x, 1u32)).collect::<Vec<_>>();
+        assert_eq!(dijkstra((0, 0), weighted, |&x| x == end), None);
+    }
+
+    #[test]
+    fn start_is_goal() {
+        let res = bfs(3, |&x| vec![x + 1], |&x| x == 3).unwrap();
+        assert_eq!(
+            res,
+            Path {
+                nodes: vec![3],
+                cost: 0
+            }
+        );
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_cells() {
+        // Entering a cell costs its digit, the direct route is expensive.
+        let maze = grid("1999\n1119\n9911");
+        let end = (2, 3);
+        let neighbours = |&x: &Position| {
+            grid_neighbours(&maze, x, |_| true)
+                .into_iter()
+                .map(|(row, column)| ((row, column), maze[row][column].to_digit(10).unwrap()))
+                .collect::<Vec<(Position, u32)>>()
+        };
+        let res = dijkstra((0, 0), neighbours, |&x| x == end).unwrap();
+        assert_eq!(res.cost, 5);
+        assert_eq!(
+            res.nodes,
+            vec![(0, 0), (1, 0), (1, 1), (1, 2), (2, 2), (2, 3)]
+        );
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra() {
+        let maze = grid(MAZE);
+        let start = find_in_grid(&maze, 'S').unwrap();
+        let end = find_in_grid(&maze, 'E').unwrap();
+        let neighbours = |x: &Position| {
+            grid_neighbours(&maze, *x, |c| c != '#')
+                .into_iter()
+                .map(|x| (x, 1usize))
+                .collect::<Vec<(Position, usize)>>()
+        };
+
+        let res = astar(start, neighbours, |&x| manhattan(x, end), |&x| x == end).unwrap();
+        let expected = dijkstra(start, neighbours, |&x| x == end).unwrap();
+        assert_eq!(res.cost, expected.cost);
+        assert_eq!(res.cost, 15);
+    }
+
+    #[test]
+    fn dijkstra_on_graph() {
+        let edges: HashMap<char, Vec<(char, i64)>> = HashMap::from([
+            ('a', vec![('b', 7), ('c', 9), ('f', 14)]),
+            ('b', vec![('c', 10), ('d', 15)]),
+            ('c', vec![('d', 11), ('f', 2)]),
+            ('d', vec![('e', 6)]),
+            ('f', vec![('e', 9)]),
+        ]);
+        let res = dijkstra(
+            'a',
+            |x| edges.get(x).cloned().unwrap_or_default(),
+            |&x| x == 'e',
+        )
+        .unwrap();
+        assert_eq!(res.cost, 20);
+        assert_eq!(res.nodes, vec!['a', 'c', 'f', 'e']);
+    }
+
+    #[test]
+    fn ragged_grid_neighbours() {
+        let maze = grid("...\n.\n...");
+        assert_eq!(
+            grid_neighbours(&maze, (1, 0), |_| true),
+            vec![(0, 0), (2, 0)]
+        );
+        assert_eq!(
+            grid_neighbours(&maze, (0, 1), |_| true),
+            vec![(0, 0), (0, 2)]
+        );
+    }
+}
@@ -1,77 +1,567 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::fmt::{Debug, Display, Write};
+use std::ops::Range;
 
-pub enum Trie {
-    Value(u32),
-    SubTree(HashMap<char, Trie>),
+use unicode_normalization::UnicodeNormalization;
+
+/// Maps keys to values, stored as one flat array of nodes.
+///
+/// Nodes are laid out level by level and the children of a node are
+/// contiguous and sorted by byte, so a lookup is a binary search per byte of
+/// the key. Keys are matched on their UTF-8 bytes.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Trie {
+    nodes: Cow<'static, [Node]>,
+    /// Length in bytes of the longest key, known when the trie is built.
+    depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Node {
+    /// Byte on the edge from the parent.
+    byte: u8,
+    child_count: u16,
+    first_child: u32,
+    value: Option<u32>,
+}
+
+impl Node {
+    const EMPTY: Node = Node {
+        byte: 0,
+        child_count: 0,
+        first_child: 0,
+        value: None,
+    };
+
+    fn children(&self) -> Range<usize> {
+        let start = self.first_child as usize;
+        return start..start + self.child_count as usize;
+    }
+}
+
+/// How text is compared to the keys of a `Trie`, keys have to be stored in
+/// the form the options produce, so lowercase when ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MatchOptions {
+    pub case_insensitive: bool,
+    pub normalisation: Option<Normalisation>,
+    /// Characters skipped inside a match, such as the `-` in `fi-ve`. A match
+    /// never starts with one.
+    pub ignored: Vec<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalisation {
+    /// Canonical composition, an `e` followed by a combining acute accent
+    /// matches `é`.
+    Nfc,
+    /// Compatibility composition, also turns fullwidth `ｏｎｅ` into `one`.
+    Nfkc,
+}
+
+/// A key found at the start of a text by `Trie::match_fuzzy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub value: u32,
+    /// Edits needed to turn the matched text into the key.
+    pub distance: usize,
+    /// Length of the matched text in bytes.
+    pub length: usize,
+}
+
+struct FuzzySearch {
+    text: Vec<char>,
+    /// Byte offset after every prefix of `text`, starting with the empty one.
+    ends: Vec<usize>,
+    max_distance: usize,
+}
+
+/// Builds a `Trie` at compile time, can be used to initialise a `static`.
+///
+/// ```
+/// use util::{static_trie, trie::Trie};
+///
+/// static TRIE: Trie = static_trie![("yes", 5), ("no", 6)];
+/// assert_eq!(TRIE.match_prefix("nope"), Some(6));
+/// ```
+#[macro_export]
+macro_rules! static_trie {
+    ($(($key:expr, $value:expr)),* $(,)?) => {{
+        const KEYS: &[(&str, u32)] = &[$(($key, $value)),*];
+        const NODES: [$crate::trie::Node; $crate::trie::node_count(KEYS)] =
+            $crate::trie::build_nodes::<{ $crate::trie::node_count(KEYS) }, { KEYS.len() }>(KEYS);
+        $crate::trie::Trie::from_static(&NODES, $crate::trie::max_key_length(KEYS))
+    }};
 }
 
 impl Trie {
+    /// Panics if a key occurs more than once.
     pub fn new(elements: Vec<(&str, u32)>) -> Trie {
-        if elements.len() == 1 && elements[0].0.len() == 0 {
-            return Trie::Value(elements[0].1);
+        let mut nodes = vec![Node::EMPTY; node_count(&elements)];
+        let mut order: Vec<usize> = (0..elements.len()).collect();
+        let mut key_of = vec![0; nodes.len()];
+        layout(&elements, &mut order, &mut key_of, &mut nodes);
+        return Trie {
+            nodes: Cow::Owned(nodes),
+            depth: max_key_length(&elements),
+        };
+    }
+
+    /// Wraps nodes built by `build_nodes` for keys at most `depth` bytes
+    /// long, see `static_trie!`.
+    pub const fn from_static(nodes: &'static [Node], depth: usize) -> Trie {
+        return Trie {
+            nodes: Cow::Borrowed(nodes),
+            depth,
+        };
+    }
+
+    pub fn match_at(&self, text: &[char], start_index: usize) -> Option<u32> {
+        let chars = text[start_index.min(text.len())..].iter().copied();
+        return self.match_bytes(utf8_bytes(chars));
+    }
+
+    /// Matches the longest key at the start of `text`.
+    pub fn match_prefix(&self, text: &str) -> Option<u32> {
+        return self.match_bytes(text.bytes());
+    }
+
+    /// Matches the longest key at the start of `text` after normalising,
+    /// folding case and leaving out ignored characters as `options` say.
+    pub fn match_prefix_with(&self, text: &str, options: &MatchOptions) -> Option<u32> {
+        if text.starts_with(options.ignored.as_slice()) {
+            return self.nodes[0].value;
         }
 
-        let mut char_map: HashMap<char, Vec<(&str, u32)>> = HashMap::new();
-        for element in elements {
-            let mut chars = element.0.chars();
+        return match options.normalisation {
+            None => self.match_chars(text.chars(), options),
+            Some(Normalisation::Nfc) => self.match_chars(text.chars().nfc(), options),
+            Some(Normalisation::Nfkc) => self.match_chars(text.chars().nfkc(), options),
+        };
+    }
+
+    /// Leaves out ignored characters and folds case of already normalised
+    /// `chars`, generic so that nothing is boxed per call.
+    fn match_chars(
+        &self,
+        chars: impl Iterator<Item = char>,
+        options: &MatchOptions,
+    ) -> Option<u32> {
+        let chars = chars.filter(|x| !options.ignored.contains(x));
+        if options.case_insensitive {
+            return self.match_bytes(utf8_bytes(chars.flat_map(char::to_lowercase)));
+        }
+        return self.match_bytes(utf8_bytes(chars));
+    }
 
-            let first_char = chars.next().unwrap();
-            if !char_map.contains_key(&first_char) {
-                char_map.insert(first_char, vec![]);
+    fn match_bytes(&self, bytes: impl Iterator<Item = u8>) -> Option<u32> {
+        let mut current = &self.nodes[0];
+        let mut res = current.value;
+        for byte in bytes {
+            let children = &self.nodes[current.children()];
+            match children.binary_search_by_key(&byte, |x| x.byte) {
+                Ok(i) => current = &children[i],
+                Err(_) => break,
+            }
+            if current.value.is_some() {
+                res = current.value;
             }
-            char_map
-                .get_mut(&first_char)
-                .unwrap()
-                .push((chars.as_str(), element.1));
         }
+        return res;
+    }
 
-        let mut final_map: HashMap<char, Trie> = HashMap::new();
-        for char_mapping in char_map {
-            final_map.insert(char_mapping.0, Trie::new(char_mapping.1));
+    /// Finds every key within `max_distance` edits of a prefix of `text`,
+    /// closest first. The distance is the Levenshtein distance, inserting,
+    /// deleting or replacing a char each count as one edit, so `fuor` is two
+    /// edits from `four`.
+    pub fn match_fuzzy(&self, text: &str, max_distance: usize) -> Vec<FuzzyMatch> {
+        let mut search = FuzzySearch {
+            text: vec![],
+            ends: vec![0],
+            max_distance,
+        };
+        for (i, c) in text.char_indices().take(self.depth() + max_distance) {
+            search.text.push(c);
+            search.ends.push(i + c.len_utf8());
         }
 
-        return Trie::SubTree(final_map);
+        let row: Vec<usize> = (0..search.ends.len()).collect();
+        let mut res: Vec<FuzzyMatch> = vec![];
+        if let Some(value) = self.nodes[0].value {
+            res.extend(search.best(value, &row));
+        }
+        self.search_fuzzy(&self.nodes[0], &search, &row, &[], &mut res);
+
+        res.sort_by_key(|x| (x.distance, Reverse(x.length)));
+        return res;
     }
 
-    pub fn match_at(&self, text: &Vec<char>, start_index: usize) -> Option<u32> {
-        return match self {
-            Trie::Value(val) => Some(*val),
-            Trie::SubTree(map) => {
-                if text.len() == start_index {
-                    return None;
-                }
+    /// Walks the children of `node`. `row` holds the distance between the
+    /// key so far and every prefix of the text, `partial` holds the bytes of a
+    /// char that is not complete yet.
+    fn search_fuzzy(
+        &self,
+        node: &Node,
+        search: &FuzzySearch,
+        row: &[usize],
+        partial: &[u8],
+        res: &mut Vec<FuzzyMatch>,
+    ) {
+        for child in &self.nodes[node.children()] {
+            let mut bytes = partial.to_vec();
+            bytes.push(child.byte);
+            let Ok(key) = std::str::from_utf8(&bytes) else {
+                self.search_fuzzy(child, search, row, &bytes, res);
+                continue;
+            };
+
+            let c = key.chars().next().unwrap();
+            let next = search.next_row(row, c);
+            if let Some(value) = child.value {
+                res.extend(search.best(value, &next));
+            }
+            if next.iter().min() <= Some(&search.max_distance) {
+                self.search_fuzzy(child, search, &next, &[], res);
+            }
+        }
+    }
+
+    /// Every key and its value, in byte order of the keys.
+    pub fn iter(&self) -> Iter<'_> {
+        return Iter {
+            trie: self,
+            stack: vec![(0, vec![])],
+        };
+    }
+
+    /// Number of nodes including the root, one per distinct prefix of the
+    /// keys in bytes.
+    pub fn node_count(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    /// Length in bytes of the longest key.
+    pub fn depth(&self) -> usize {
+        return self.depth;
+    }
+
+    /// The nodes and edges as a Graphviz graph, nodes holding a value are
+    /// drawn as a double circle labelled with the value.
+    pub fn to_dot(&self) -> String {
+        let mut res = String::from("digraph trie {\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let (label, shape) = match node.value {
+                Some(value) => (value.to_string(), "doublecircle"),
+                None => (String::new(), "circle"),
+            };
+            writeln!(res, "    n{} [label=\"{}\", shape={}];", i, label, shape).unwrap();
+            for child in node.children() {
+                let byte = dot_label(self.nodes[child].byte);
+                writeln!(res, "    n{} -> n{} [label=\"{}\"];", i, child, byte).unwrap();
+            }
+        }
+        res.push_str("}\n");
+        return res;
+    }
+
+    /// Writes the children of the node at `index` one char per line,
+    /// indented by their depth in chars.
+    fn write_tree(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        index: usize,
+        depth: usize,
+        partial: &[u8],
+    ) -> std::fmt::Result {
+        for child in self.nodes[index].children() {
+            let mut bytes = partial.to_vec();
+            bytes.push(self.nodes[child].byte);
+            let Ok(key) = std::str::from_utf8(&bytes) else {
+                self.write_tree(f, child, depth, &bytes)?;
+                continue;
+            };
+
+            write!(
+                f,
+                "{:indent$}{}",
+                "",
+                key.escape_debug(),
+                indent = depth * 2
+            )?;
+            if let Some(value) = self.nodes[child].value {
+                write!(f, " = {}", value)?;
+            }
+            writeln!(f)?;
+            self.write_tree(f, child, depth + 1, &[])?;
+        }
+        return Ok(());
+    }
+}
+
+fn dot_label(byte: u8) -> String {
+    return match byte {
+        b'"' | b'\\' => format!("\\{}", byte as char),
+        _ if byte.is_ascii_graphic() || byte == b' ' => (byte as char).to_string(),
+        _ => format!("0x{:02X}", byte),
+    };
+}
+
+impl Debug for Trie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.debug_map().entries(self.iter()).finish();
+    }
+}
+
+impl Display for Trie {
+    /// An indented tree with one char per line, followed by ` = value` when
+    /// a key ends there.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(value) = self.nodes[0].value {
+            writeln!(f, "= {}", value)?;
+        }
+        return self.write_tree(f, 0, 0, &[]);
+    }
+}
+
+/// The keys and values of a `Trie`, see `Trie::iter`.
+pub struct Iter<'a> {
+    trie: &'a Trie,
+    stack: Vec<(usize, Vec<u8>)>,
+}
 
-                let c = text[start_index];
+impl<'a> Iterator for Iter<'a> {
+    type Item = (String, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((index, key)) = self.stack.pop() {
+            let node = &self.trie.nodes[index];
+            for child in node.children().rev() {
+                let mut child_key = key.clone();
+                child_key.push(self.trie.nodes[child].byte);
+                self.stack.push((child, child_key));
+            }
+            if let Some(value) = node.value {
+                return Some((String::from_utf8(key).unwrap(), value));
+            }
+        }
+        return None;
+    }
+}
+
+impl<'a> IntoIterator for &'a Trie {
+    type Item = (String, u32);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.iter();
+    }
+}
+
+impl FuzzySearch {
+    fn next_row(&self, row: &[usize], c: char) -> Vec<usize> {
+        let mut next = vec![row[0] + 1];
+        for j in 1..row.len() {
+            let cost = if self.text[j - 1] == c { 0 } else { 1 };
+            next.push((row[j] + 1).min(next[j - 1] + 1).min(row[j - 1] + cost));
+        }
+        return next;
+    }
+
+    /// The closest prefix of the text, the shortest one on ties.
+    fn best(&self, value: u32, row: &[usize]) -> Option<FuzzyMatch> {
+        let (i, distance) = row.iter().enumerate().min_by_key(|(_, x)| **x)?;
+        if *distance > self.max_distance {
+            return None;
+        }
+        return Some(FuzzyMatch {
+            value,
+            distance: *distance,
+            length: self.ends[i],
+        });
+    }
+}
+
+fn utf8_bytes(chars: impl Iterator<Item = char>) -> impl Iterator<Item = u8> {
+    return chars.flat_map(|c| {
+        let mut buffer = [0; 4];
+        let length = c.encode_utf8(&mut buffer).len();
+        return buffer.into_iter().take(length);
+    });
+}
+
+/// Length in bytes of the longest of `keys`.
+pub const fn max_key_length(keys: &[(&str, u32)]) -> usize {
+    let mut res = 0;
+    let mut i = 0;
+    while i < keys.len() {
+        if keys[i].0.len() > res {
+            res = keys[i].0.len();
+        }
+        i += 1;
+    }
+    return res;
+}
 
-                if !map.contains_key(&c) {
-                    return None;
+/// Number of nodes a trie over `keys` consists of, including the root.
+pub const fn node_count(keys: &[(&str, u32)]) -> usize {
+    let mut count = 1;
+    let mut i = 0;
+    while i < keys.len() {
+        let key = keys[i].0.as_bytes();
+        let mut length = 1;
+        while length <= key.len() {
+            let mut seen = false;
+            let mut j = 0;
+            while j < i && !seen {
+                let other = keys[j].0.as_bytes();
+                seen = other.len() >= length && same_prefix(key, other, length);
+                j += 1;
+            }
+            if !seen {
+                count += 1;
+            }
+            length += 1;
+        }
+        i += 1;
+    }
+    return count;
+}
+
+/// Lays out the nodes of a trie over `keys`, `N` has to be `node_count(keys)`
+/// and `K` the number of keys.
+pub const fn build_nodes<const N: usize, const K: usize>(keys: &[(&str, u32)]) -> [Node; N] {
+    let mut nodes = [Node::EMPTY; N];
+    let mut order = [0; K];
+    let mut key_of = [0; N];
+    let mut i = 0;
+    while i < K {
+        order[i] = i;
+        i += 1;
+    }
+    layout(keys, &mut order, &mut key_of, &mut nodes);
+    return nodes;
+}
+
+/// Sorts the keys and adds the nodes of one level after the other, so the
+/// children of every node end up next to each other in byte order.
+const fn layout(
+    keys: &[(&str, u32)],
+    order: &mut [usize],
+    key_of: &mut [usize],
+    nodes: &mut [Node],
+) {
+    let mut i = 1;
+    while i < order.len() {
+        let mut j = i;
+        while j > 0 && is_less(keys[order[j]].0.as_bytes(), keys[order[j - 1]].0.as_bytes()) {
+            let swap = order[j];
+            order[j] = order[j - 1];
+            order[j - 1] = swap;
+            j -= 1;
+        }
+        i += 1;
+    }
+
+    let mut next = 1;
+    let mut level_start = 0;
+    let mut level_end = 1;
+    let mut depth = 0;
+    while level_start < level_end {
+        let mut parent = level_start;
+        let mut k = 0;
+        while k < order.len() {
+            let key = keys[order[k]].0.as_bytes();
+            let value = keys[order[k]].1;
+            k += 1;
+
+            if depth == 0 && key.is_empty() {
+                if nodes[0].value.is_some() {
+                    panic!("Trie keys must be unique");
                 }
+                nodes[0].value = Some(value);
+            }
+            if key.len() <= depth {
+                continue;
+            }
 
-                return map.get(&c).unwrap().match_at(text, start_index + 1);
+            while depth > 0 && !same_prefix(keys[key_of[parent]].0.as_bytes(), key, depth) {
+                parent += 1;
             }
-        };
+            if next > level_end && same_prefix(keys[key_of[next - 1]].0.as_bytes(), key, depth + 1)
+            {
+                if key.len() == depth + 1 {
+                    panic!("Trie keys must be unique");
+                }
+                continue;
+            }
+
+            nodes[next] = Node {
+                byte: key[depth],
+                child_count: 0,
+                first_child: 0,
+                value: if key.len() == depth + 1 {
+                    Some(value)
+                } else {
+                    None
+                },
+            };
+            key_of[next] = order[k - 1];
+            if nodes[parent].child_count == 0 {
+                nodes[parent].first_child = next as u32;
+            }
+            nodes[parent].child_count += 1;
+            next += 1;
+        }
+
+        level_start = level_end;
+        level_end = next;
+        depth += 1;
     }
 }
 
+const fn same_prefix(a: &[u8], b: &[u8], length: usize) -> bool {
+    let mut i = 0;
+    while i < length {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    return true;
+}
+
+const fn is_less(a: &[u8], b: &[u8]) -> bool {
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+        i += 1;
+    }
+    return a.len() < b.len();
+}
+
 #[cfg(test)]
+#[allow(clippy::redundant_pattern_matching)]
 mod tests {
     use super::*;
 
     #[test]
     fn new_trie() {
         let res = Trie::new(vec![("yes", 5), ("no", 6)]);
-        if let Trie::SubTree(map) = res {
-            assert!(map.contains_key(&'y'));
-            assert!(map.contains_key(&'n'));
-        } else {
-            panic!("Trie is not a SubTree");
-        }
+        assert_eq!(res.node_count(), 6);
+        assert_eq!(res.depth(), 3);
+        assert_eq!(
+            res.iter().collect::<Vec<(String, u32)>>(),
+            vec![(String::from("no"), 6), (String::from("yes"), 5)]
+        );
     }
 
     #[test]
     fn match_found() {
         let sut = Trie::new(vec![("yes", 5), ("no", 6)]);
-        let res = sut.match_at(&"yesitis".chars().collect(), 0);
+        let res = sut.match_at(&"yesitis".chars().collect::<Vec<char>>(), 0);
         if let Some(value) = res {
             assert_eq!(value, 5);
         } else {
@@ -82,9 +572,220 @@ mod tests {
     #[test]
     fn match_not_found() {
         let sut = Trie::new(vec![("yes", 5), ("no", 6)]);
-        let res = sut.match_at(&"yesitis".chars().collect(), 1);
+        let res = sut.match_at(&"yesitis".chars().collect::<Vec<char>>(), 1);
         if let Some(_) = res {
             panic!("result should not be found");
         }
     }
+
+    #[test]
+    fn match_prefix_found() {
+        let sut = Trie::new(vec![("yes", 5), ("no", 6)]);
+        assert_eq!(sut.match_prefix("nope"), Some(6));
+        assert_eq!(sut.match_prefix("ye"), None);
+        assert_eq!(sut.match_prefix("maybe"), None);
+    }
+
+    #[test]
+    fn match_longest_key() {
+        let sut = Trie::new(vec![("one", 1), ("on", 2), ("", 3), ("ones", 4)]);
+        assert_eq!(sut.match_prefix("onex"), Some(1));
+        assert_eq!(sut.match_prefix("ox"), Some(3));
+        assert_eq!(sut.match_prefix("ones"), Some(4));
+        assert_eq!(
+            sut.match_at(&"xon".chars().collect::<Vec<char>>(), 1),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn multi_byte_keys() {
+        let sut = Trie::new(vec![("één", 1), ("eén", 2), ("→", 3)]);
+        assert_eq!(sut.match_prefix("één!"), Some(1));
+        assert_eq!(sut.match_prefix("eén"), Some(2));
+        assert_eq!(
+            sut.match_at(&"a→".chars().collect::<Vec<char>>(), 1),
+            Some(3)
+        );
+        assert_eq!(sut.match_prefix("é"), None);
+    }
+
+    #[test]
+    fn static_matches_new() {
+        static SUT: Trie = static_trie![("three", 3), ("two", 2), ("one", 1), ("ten", 10)];
+        let expected = Trie::new(vec![("three", 3), ("two", 2), ("one", 1), ("ten", 10)]);
+        assert_eq!(SUT, expected);
+        assert_eq!(SUT.match_prefix("twone"), Some(2));
+    }
+
+    #[test]
+    fn empty_trie() {
+        let sut = Trie::new(vec![]);
+        assert_eq!(sut.match_prefix("anything"), None);
+        assert_eq!(sut.match_at(&[], 0), None);
+    }
+
+    #[test]
+    fn match_ignoring_case() {
+        let sut = Trie::new(vec![("one", 1), ("straße", 2)]);
+        let options = MatchOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        assert_eq!(sut.match_prefix("One"), None);
+        assert_eq!(sut.match_prefix_with("One", &options), Some(1));
+        assert_eq!(sut.match_prefix_with("ONEs", &options), Some(1));
+        assert_eq!(sut.match_prefix_with("STRAßE", &options), Some(2));
+        assert_eq!(sut.match_prefix_with("on", &options), None);
+    }
+
+    #[test]
+    fn match_normalised() {
+        let sut = Trie::new(vec![("één", 1), ("one", 2)]);
+        let decomposed = "e\u{301}e\u{301}n";
+        let nfc = MatchOptions {
+            normalisation: Some(Normalisation::Nfc),
+            ..Default::default()
+        };
+        let nfkc = MatchOptions {
+            normalisation: Some(Normalisation::Nfkc),
+            ..Default::default()
+        };
+        assert_eq!(sut.match_prefix(decomposed), None);
+        assert_eq!(sut.match_prefix_with(decomposed, &nfc), Some(1));
+        assert_eq!(sut.match_prefix_with("ｏｎｅ", &nfc), None);
+        assert_eq!(sut.match_prefix_with("ｏｎｅ", &nfkc), Some(2));
+    }
+
+    #[test]
+    fn match_skipping_ignored() {
+        let sut = Trie::new(vec![("five", 5), ("", 0)]);
+        let options = MatchOptions {
+            ignored: vec!['-', '_'],
+            ..Default::default()
+        };
+        assert_eq!(sut.match_prefix_with("fi-v_e", &options), Some(5));
+        assert_eq!(sut.match_prefix_with("f--ive", &options), Some(5));
+        assert_eq!(sut.match_prefix_with("-five", &options), Some(0));
+        assert_eq!(sut.match_prefix_with("fi ve", &options), Some(0));
+    }
+
+    #[test]
+    fn default_options_match_exactly() {
+        let sut = Trie::new(vec![("one", 1), ("two", 2)]);
+        for text in ["one", "One", "tw-o", "twone", ""] {
+            assert_eq!(
+                sut.match_prefix_with(text, &MatchOptions::default()),
+                sut.match_prefix(text)
+            );
+        }
+    }
+
+    #[test]
+    fn match_fuzzy_exact() {
+        let sut = Trie::new(vec![("seven", 7), ("six", 6)]);
+        let res = sut.match_fuzzy("sevenish", 0);
+        assert_eq!(
+            res,
+            vec![FuzzyMatch {
+                value: 7,
+                distance: 0,
+                length: 5
+            }]
+        );
+        assert_eq!(sut.match_fuzzy("sevn", 0), vec![]);
+    }
+
+    #[test]
+    fn match_fuzzy_edits() {
+        let sut = Trie::new(vec![("seven", 7), ("four", 4), ("nine", 9)]);
+        let best = |text: &str| sut.match_fuzzy(text, 1).first().copied();
+        let sevn = best("sevn!").unwrap();
+        assert_eq!((sevn.value, sevn.distance, sevn.length), (7, 1, 4));
+        let fuur = best("fuur").unwrap();
+        assert_eq!((fuur.value, fuur.distance, fuur.length), (4, 1, 4));
+        // Swapping two chars takes two edits.
+        assert_eq!(best("fuor"), None);
+        let fuor = sut.match_fuzzy("fuor", 2)[0];
+        assert_eq!((fuor.value, fuor.distance), (4, 2));
+        let niine = best("niine").unwrap();
+        assert_eq!((niine.value, niine.distance, niine.length), (9, 1, 5));
+        let nxne = best("nxne").unwrap();
+        assert_eq!((nxne.value, nxne.distance, nxne.length), (9, 1, 4));
+        assert_eq!(best("sxvxn"), None);
+    }
+
+    #[test]
+    fn match_fuzzy_closest_first() {
+        let sut = Trie::new(vec![("one", 1), ("on", 2), ("nine", 9)]);
+        let res: Vec<(u32, usize)> = sut
+            .match_fuzzy("onex", 2)
+            .iter()
+            .map(|x| (x.value, x.distance))
+            .collect();
+        assert_eq!(res, vec![(1, 0), (2, 0), (9, 2)]);
+    }
+
+    #[test]
+    fn match_fuzzy_counts_chars() {
+        let sut = Trie::new(vec![("één", 1)]);
+        let res = sut.match_fuzzy("eén", 1);
+        assert_eq!(res.len(), 1);
+        assert_eq!((res[0].distance, res[0].length), (1, 4));
+        assert_eq!(sut.match_fuzzy("een", 1), vec![]);
+    }
+
+    #[test]
+    fn iter_in_key_order() {
+        let sut = Trie::new(vec![("two", 2), ("", 0), ("één", 1), ("tw", 3), ("one", 4)]);
+        let res: Vec<(String, u32)> = sut.iter().collect();
+        let keys: Vec<&str> = res.iter().map(|x| x.0.as_str()).collect();
+        assert_eq!(keys, vec!["", "one", "tw", "two", "één"]);
+        assert_eq!(sut.iter().count(), 5);
+        assert_eq!(
+            format!("{:?}", sut),
+            r#"{"": 0, "one": 4, "tw": 3, "two": 2, "één": 1}"#
+        );
+    }
+
+    #[test]
+    fn empty_trie_shape() {
+        let sut = Trie::new(vec![]);
+        assert_eq!(sut.node_count(), 1);
+        assert_eq!(sut.depth(), 0);
+        assert_eq!(sut.iter().next(), None);
+        assert_eq!(sut.to_string(), "");
+    }
+
+    #[test]
+    fn display_indented_tree() {
+        let sut = Trie::new(vec![("on", 1), ("one", 2), ("é", 3)]);
+        assert_eq!(sut.to_string(), "o\n  n = 1\n    e = 2\né = 3\n");
+        assert_eq!(sut.depth(), 3);
+        assert_eq!(sut.node_count(), 6);
+    }
+
+    #[test]
+    fn dot_export() {
+        let sut = Trie::new(vec![("a\"", 1), ("é", 2)]);
+        let expected = r#"digraph trie {
+    n0 [label="", shape=circle];
+    n0 -> n1 [label="a"];
+    n0 -> n2 [label="0xC3"];
+    n1 [label="", shape=circle];
+    n1 -> n3 [label="\""];
+    n2 [label="", shape=circle];
+    n2 -> n4 [label="0xA9"];
+    n3 [label="1", shape=doublecircle];
+    n4 [label="2", shape=doublecircle];
+}
+"#;
+        assert_eq!(sut.to_dot(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Trie keys must be unique")]
+    fn duplicate_keys() {
+        Trie::new(vec![("yes", 5), ("no", 6), ("yes", 7)]);
+    }
 }